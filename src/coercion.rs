@@ -0,0 +1,172 @@
+use crate::schema_walker::{pointer_push, Branches, SchemaWalker, Scope};
+use serde_json::{Number, Value};
+
+/// Converts values of `instance` to the types the main schema expects:
+/// strings to numbers, integers and booleans, single values to arrays.
+/// Returns `true` if anything was changed.
+pub fn coerce_types(walker: &SchemaWalker, branches: &Branches, instance: &mut Value) -> bool {
+    walker
+        .root()
+        .is_some_and(|root| coerce(walker, root, branches, instance, ""))
+}
+
+fn coerce<'a>(
+    walker: &'a SchemaWalker,
+    scope: Scope<'a>,
+    branches: &Branches,
+    instance: &mut Value,
+    instance_path: &str,
+) -> bool {
+    let scopes = walker.in_place(scope, branches, instance_path);
+    let mut changed = false;
+
+    for scope in &scopes {
+        let types = expected_types(scope.schema);
+        if types.is_empty() || types.iter().any(|t| has_type(instance, t)) {
+            continue;
+        }
+
+        if let Some(coerced) = coerce_value(instance, &types) {
+            *instance = coerced;
+            changed = true;
+        }
+    }
+
+    match instance {
+        Value::Object(map) => {
+            for (name, value) in map.iter_mut() {
+                let path = pointer_push(instance_path, name);
+                for scope in &scopes {
+                    if let Some(property) = walker.property(scope, name) {
+                        changed |= coerce(walker, property, branches, value, &path);
+                    }
+                }
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                let path = pointer_push(instance_path, &index.to_string());
+                for scope in &scopes {
+                    if let Some(item_scope) = walker.item(scope, index) {
+                        changed |= coerce(walker, item_scope, branches, item, &path);
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+
+    changed
+}
+
+fn expected_types(schema: &Value) -> Vec<&str> {
+    match schema.get("type") {
+        Some(Value::String(name)) => vec![name.as_str()],
+        Some(Value::Array(names)) => names.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn has_type(value: &Value, type_name: &str) -> bool {
+    match (type_name, value) {
+        ("null", Value::Null)
+        | ("boolean", Value::Bool(_))
+        | ("number", Value::Number(_))
+        | ("string", Value::String(_))
+        | ("array", Value::Array(_))
+        | ("object", Value::Object(_)) => true,
+        ("integer", Value::Number(n)) => {
+            n.is_i64() || n.is_u64() || n.as_f64().is_some_and(|f| f.fract() == 0.0)
+        }
+        _ => false,
+    }
+}
+
+fn coerce_value(value: &Value, types: &[&str]) -> Option<Value> {
+    if let Value::String(text) = value {
+        let text = text.trim();
+
+        for type_name in types {
+            let coerced = match *type_name {
+                "boolean" => match text {
+                    "true" => Some(Value::Bool(true)),
+                    "false" => Some(Value::Bool(false)),
+                    _ => None,
+                },
+                "number" | "integer" => text.parse::<Number>().ok().map(Value::Number),
+                _ => None,
+            };
+
+            if let Some(coerced) = coerced.filter(|c| has_type(c, type_name)) {
+                return Some(coerced);
+            }
+        }
+    }
+
+    if types.contains(&"array") && !value.is_array() {
+        return Some(Value::Array(vec![value.clone()]));
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::coerce_types;
+    use crate::schema_walker::testing::walker;
+    use crate::schema_walker::Branches;
+    use serde_json::{json, Value};
+
+    fn coerced(schema: &Value, mut instance: Value) -> Value {
+        let walker = walker(schema);
+        coerce_types(&walker, &Branches::default(), &mut instance);
+        instance
+    }
+
+    #[test]
+    fn scalars_from_strings() {
+        let schema = json!({
+            "properties": {
+                "sum": {"type": "number"},
+                "count": {"type": "integer"},
+                "paid": {"type": "boolean"},
+                "code": {"type": "string"},
+                "comment": {"type": ["integer", "null"]}
+            }
+        });
+
+        assert_eq!(
+            coerced(
+                &schema,
+                json!({"sum": "10.5", "count": " 3 ", "paid": "false", "code": "007", "comment": "text"})
+            ),
+            json!({"sum": 10.5, "count": 3, "paid": false, "code": "007", "comment": "text"})
+        );
+    }
+
+    #[test]
+    fn integer_is_not_taken_from_fraction() {
+        let schema = json!({"type": "integer"});
+        assert_eq!(coerced(&schema, json!("1.5")), json!("1.5"));
+    }
+
+    #[test]
+    fn single_value_to_array() {
+        let schema = json!({
+            "$defs": {"line": {"type": "object", "properties": {"qty": {"type": "integer"}}}},
+            "properties": {
+                "tags": {"type": "array", "items": {"type": "string"}},
+                "lines": {"type": "array", "items": {"$ref": "#/$defs/line"}},
+                "ids": {"type": "array", "items": {"type": "integer"}}
+            }
+        });
+
+        assert_eq!(
+            coerced(
+                &schema,
+                json!({"tags": "vip", "lines": {"qty": "2"}, "ids": ["1", "2"]})
+            ),
+            json!({"tags": ["vip"], "lines": [{"qty": 2}], "ids": [1, 2]})
+        );
+    }
+}
@@ -0,0 +1,238 @@
+use crate::error_details::keyword;
+use crate::labels::{evaluation_tokens, keyword_position};
+use crate::schema_walker::SchemaWalker;
+use jsonschema::error::ValidationErrorKind;
+use jsonschema::ValidationError;
+use serde_json::Value;
+use std::collections::HashSet;
+
+pub const ERROR_MESSAGE: &str = "errorMessage";
+
+/// Message declared for the error with the `errorMessage` keyword, as in ajv-errors:
+///
+/// - `"errorMessage": "..."` replaces errors of the subschema and of everything below it;
+/// - `"errorMessage": {"<keyword>": "...", "_": "..."}` replaces errors of the subschema's
+///   keywords, `_` covers keywords not listed;
+/// - `"required": {"<property>": "..."}` sets a message per missing property;
+/// - `"properties": {"<property>": "..."}` replaces errors inside the property's subschema.
+///
+/// The nearest declaration to the failed keyword wins. `${0}` in the message is replaced with
+/// the failing value, `${0/pointer}` with a value inside it.
+pub fn custom_message(
+    walker: &SchemaWalker,
+    error: &ValidationError,
+    instance: &Value,
+) -> Option<String> {
+    let (_, _, template) = declaration(walker, error)?;
+    Some(interpolate(template, instance))
+}
+
+/// Drops errors replaced by the same string `errorMessage` at the same instance location as
+/// an earlier one: ajv-errors reports such a subschema once, not once per failed keyword.
+pub fn merge_declared<'e>(
+    walker: &SchemaWalker,
+    errors: Vec<ValidationError<'e>>,
+) -> Vec<ValidationError<'e>> {
+    let mut reported = HashSet::new();
+
+    errors
+        .into_iter()
+        .filter(|error| match declaration(walker, error) {
+            Some((end, Value::String(_), _)) => {
+                let tokens = evaluation_tokens(error);
+                reported.insert((tokens[..end].join("/"), error.instance_path().to_string()))
+            }
+            _ => true,
+        })
+        .collect()
+}
+
+/// Nearest `errorMessage` with a message for the error: the length of the evaluation path
+/// to its subschema, the declaration and the message template.
+fn declaration<'w>(
+    walker: &'w SchemaWalker,
+    error: &ValidationError,
+) -> Option<(usize, &'w Value, &'w str)> {
+    let tokens = evaluation_tokens(error);
+    let position = keyword_position(error, &tokens);
+
+    (1..=position).rev().find_map(|end| {
+        let declared = walker
+            .at_evaluation_path(&tokens[..end].join("/"))?
+            .schema
+            .get(ERROR_MESSAGE)?;
+
+        let template = if end == position {
+            own_message(declared, error)
+        } else {
+            nested_message(declared, &tokens[end..])
+        }?;
+        Some((end, declared, template))
+    })
+}
+
+/// Message for a keyword of the subschema `errorMessage` is declared in.
+fn own_message<'a>(declared: &'a Value, error: &ValidationError) -> Option<&'a str> {
+    if let Value::String(message) = declared {
+        return Some(message);
+    }
+
+    let by_keyword = match (declared.get(keyword(error.kind())), error.kind()) {
+        (Some(Value::Object(by_property)), ValidationErrorKind::Required { property }) => property
+            .as_str()
+            .and_then(|property| by_property.get(property)),
+        (found, _) => found,
+    };
+
+    by_keyword
+        .or_else(|| declared.get("_"))
+        .and_then(Value::as_str)
+}
+
+/// Message for an error raised below the subschema, `rest` is the path to it.
+fn nested_message<'a>(declared: &'a Value, rest: &[&str]) -> Option<&'a str> {
+    if let Value::String(message) = declared {
+        return Some(message);
+    }
+
+    match rest {
+        ["properties", name, ..] => {
+            let name = name.replace("~1", "/").replace("~0", "~");
+            declared.get("properties")?.get(&name)?.as_str()
+        }
+        _ => None,
+    }
+}
+
+fn interpolate(template: &str, instance: &Value) -> String {
+    let mut message = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find("${0") {
+        let Some(length) = rest[start..].find('}') else {
+            break;
+        };
+
+        let pointer = &rest[start + 3..start + length];
+        message.push_str(&rest[..start]);
+
+        match instance.pointer(pointer) {
+            Some(Value::String(text)) => message.push_str(text),
+            Some(value) => message.push_str(&value.to_string()),
+            None => message.push_str(&rest[start..=start + length]),
+        }
+
+        rest = &rest[start + length + 1..];
+    }
+
+    message.push_str(rest);
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{custom_message, interpolate, merge_declared};
+    use crate::schema_walker::testing::{map_errors, walker};
+    use serde_json::{json, Value};
+
+    fn messages(schema: &Value, instance: &Value) -> Vec<Option<String>> {
+        map_errors(schema, instance, |walker, error| {
+            custom_message(walker, error, error.instance())
+        })
+    }
+
+    #[test]
+    fn per_keyword_and_property() {
+        let schema = json!({
+            "properties": {
+                "kpp": {
+                    "type": "string",
+                    "pattern": "^[0-9]{9}$",
+                    "errorMessage": {"pattern": "КПП ${0} должен состоять из 9 цифр"}
+                },
+                "lines": {"items": {"properties": {"qty": {"minimum": 1}}}},
+                "inn": {"type": "string"}
+            },
+            "required": ["inn", "kpp"],
+            "errorMessage": {
+                "required": {"inn": "Не заполнен ИНН"},
+                "properties": {"lines": "Неверная строка товаров"}
+            }
+        });
+
+        assert_eq!(
+            messages(&schema, &json!({"kpp": "123456789"})),
+            vec![Some("Не заполнен ИНН".to_string())]
+        );
+        assert_eq!(
+            messages(&schema, &json!({"inn": "1", "kpp": "12"})),
+            vec![Some("КПП 12 должен состоять из 9 цифр".to_string())]
+        );
+        assert_eq!(
+            messages(&schema, &json!({"inn": 1, "kpp": "123456789"})),
+            vec![None]
+        );
+        assert_eq!(
+            messages(
+                &schema,
+                &json!({"inn": "1", "kpp": "123456789", "lines": [{"qty": 0}]})
+            ),
+            vec![Some("Неверная строка товаров".to_string())]
+        );
+    }
+
+    #[test]
+    fn string_and_fallback() {
+        let schema = json!({
+            "$defs": {"date": {"type": "string", "maxLength": 10, "errorMessage": "Неверная дата"}},
+            "properties": {
+                "date": {"$ref": "#/$defs/date"},
+                "sum": {"type": "number", "minimum": 0, "errorMessage": {"_": "Неверная сумма ${0}"}}
+            }
+        });
+
+        assert_eq!(
+            messages(&schema, &json!({"date": 1, "sum": -1})),
+            vec![
+                Some("Неверная дата".to_string()),
+                Some("Неверная сумма -1".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn string_declaration_reported_once() {
+        let schema = json!({
+            "properties": {
+                "kpp": {
+                    "type": "string",
+                    "pattern": "^[0-9]+$",
+                    "minLength": 9,
+                    "errorMessage": "Неверный КПП"
+                },
+                "inn": {"pattern": "^[0-9]+$", "minLength": 10}
+            }
+        });
+        let walker = walker(&schema);
+        let validator = jsonschema::validator_for(&schema).unwrap();
+
+        let instance = json!({"kpp": "ab", "inn": "ab"});
+        let errors = merge_declared(&walker, validator.iter_errors(&instance).collect());
+        let messages: Vec<_> = errors
+            .iter()
+            .map(|error| custom_message(&walker, error, error.instance()))
+            .collect();
+
+        // Errors without a string declaration are all kept
+        assert_eq!(messages, vec![None, None, Some("Неверный КПП".to_string())]);
+    }
+
+    #[test]
+    fn interpolation() {
+        let value = json!({"name": "Ann", "age": 5});
+        assert_eq!(
+            interpolate("${0/name} (${0/age}), ${0/x}, ${0", &value),
+            "Ann (5), ${0/x}, ${0"
+        );
+    }
+}
@@ -0,0 +1,187 @@
+use crate::schema_walker::{pointer_push, Branches, SchemaWalker, Scope};
+use serde_json::Value;
+
+/// Fills in properties missing from `instance` with `default` values of the main schema.
+pub fn apply_defaults(walker: &SchemaWalker, branches: &Branches, instance: &mut Value) {
+    if let Some(root) = walker.root() {
+        apply(walker, root, branches, instance, "");
+    }
+}
+
+fn apply<'a>(
+    walker: &'a SchemaWalker,
+    scope: Scope<'a>,
+    branches: &Branches,
+    instance: &mut Value,
+    instance_path: &str,
+) {
+    let scopes = walker.in_place(scope, branches, instance_path);
+
+    match instance {
+        Value::Object(map) => {
+            for scope in &scopes {
+                let Some(properties) = scope.schema.get("properties").and_then(Value::as_object)
+                else {
+                    continue;
+                };
+
+                for name in properties.keys() {
+                    if map.contains_key(name) {
+                        continue;
+                    }
+
+                    let default = walker
+                        .property(scope, name)
+                        .and_then(|property| default_value(walker, property));
+                    if let Some(default) = default {
+                        map.insert(name.clone(), default.clone());
+                    }
+                }
+            }
+
+            for (name, value) in map.iter_mut() {
+                let path = pointer_push(instance_path, name);
+                for scope in &scopes {
+                    if let Some(property) = walker.property(scope, name) {
+                        apply(walker, property, branches, value, &path);
+                    }
+                }
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                let path = pointer_push(instance_path, &index.to_string());
+                for scope in &scopes {
+                    if let Some(item_scope) = walker.item(scope, index) {
+                        apply(walker, item_scope, branches, item, &path);
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// `default` of the property schema or of the schemas it refers to through `$ref` / `allOf`.
+fn default_value<'a>(walker: &'a SchemaWalker, scope: Scope<'a>) -> Option<&'a Value> {
+    walker
+        .in_place(scope, &Branches::default(), "")
+        .into_iter()
+        .find_map(|scope| scope.schema.get("default"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::apply_defaults;
+    use crate::schema_walker::{Branches, SchemaWalker, DEFAULT_BASE_URI};
+    use serde_json::{json, Value};
+    use std::collections::HashMap;
+
+    fn with_defaults(schema: &Value, instance: Value) -> Value {
+        with_stored_defaults(schema, instance, &HashMap::new())
+    }
+
+    fn with_stored_defaults(
+        schema: &Value,
+        mut instance: Value,
+        store: &HashMap<jsonschema::Uri<String>, Value>,
+    ) -> Value {
+        let walker = SchemaWalker::new(schema, DEFAULT_BASE_URI, store, None).unwrap();
+        let mut options = jsonschema::options();
+        for (uri, contents) in store {
+            let resource = jsonschema::Resource::from_contents(contents.clone());
+            options = options.with_resource(uri.as_str(), resource);
+        }
+        let validator = options.build(schema).unwrap();
+        let branches = Branches::new(&validator.evaluate(&instance)).unwrap();
+
+        apply_defaults(&walker, &branches, &mut instance);
+        instance
+    }
+
+    #[test]
+    fn nested_properties_and_items() {
+        let schema = json!({
+            "$defs": {
+                "currency": {"type": "string", "default": "RUB"},
+                "line": {
+                    "properties": {
+                        "quantity": {"default": 1},
+                        "currency": {"$ref": "#/$defs/currency"}
+                    }
+                }
+            },
+            "properties": {
+                "status": {"default": "new"},
+                "lines": {"items": {"$ref": "#/$defs/line"}}
+            },
+            "allOf": [{"properties": {"source": {"default": "1c"}}}]
+        });
+
+        assert_eq!(
+            with_defaults(
+                &schema,
+                json!({"lines": [{"quantity": 3}, {"currency": "USD"}]})
+            ),
+            json!({
+                "status": "new",
+                "source": "1c",
+                "lines": [
+                    {"quantity": 3, "currency": "RUB"},
+                    {"quantity": 1, "currency": "USD"}
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn only_matched_branch() {
+        let schema = json!({
+            "properties": {"kind": {"type": "string"}},
+            "if": {"properties": {"kind": {"const": "legal"}}},
+            "then": {"properties": {"kpp": {"default": ""}}},
+            "else": {"properties": {"snils": {"default": ""}}}
+        });
+
+        assert_eq!(
+            with_defaults(&schema, json!({"kind": "legal"})),
+            json!({"kind": "legal", "kpp": ""})
+        );
+        assert_eq!(
+            with_defaults(&schema, json!({"kind": "individual"})),
+            json!({"kind": "individual", "snils": ""})
+        );
+    }
+
+    #[test]
+    fn referenced_schema_from_store() {
+        let uri = jsonschema::Uri::parse("https://example.com/party.json".to_string()).unwrap();
+        let party = json!({
+            "$id": "https://example.com/party.json",
+            "properties": {"country": {"$ref": "#/$defs/country"}},
+            "$defs": {"country": {"default": "RU"}}
+        });
+        let schema = json!({
+            "$id": "https://example.com/order.json",
+            "properties": {"buyer": {"$ref": "party.json"}}
+        });
+
+        assert_eq!(
+            with_stored_defaults(
+                &schema,
+                json!({"buyer": {}}),
+                &HashMap::from([(uri, party)])
+            ),
+            json!({"buyer": {"country": "RU"}})
+        );
+    }
+
+    #[test]
+    fn existing_values_are_kept() {
+        let schema = json!({"properties": {"status": {"default": "new"}}});
+        assert_eq!(
+            with_defaults(&schema, json!({"status": null})),
+            json!({"status": null})
+        );
+    }
+}
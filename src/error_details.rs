@@ -0,0 +1,198 @@
+use jsonschema::error::{TypeKind, ValidationErrorKind};
+use jsonschema::ValidationError;
+use serde_json::{json, Map, Value};
+
+pub fn error_details(
+    error: &ValidationError,
+    instance_path: String,
+    instance_path_1c: String,
+    instance: &Value,
+    message: String,
+) -> Map<String, Value> {
+    let mut details = Map::new();
+    details.insert("instancePath".to_string(), Value::String(instance_path));
+    details.insert(
+        "instancePath1C".to_string(),
+        Value::String(instance_path_1c),
+    );
+    details.insert(
+        "schemaPath".to_string(),
+        Value::String(error.schema_path().to_string()),
+    );
+    details.insert(
+        "keyword".to_string(),
+        Value::String(keyword(error.kind()).to_string()),
+    );
+    details.insert("instance".to_string(), instance.clone());
+    details.insert("message".to_string(), Value::String(message));
+    details.insert("params".to_string(), params(error.kind()));
+    details
+}
+
+pub fn keyword(kind: &ValidationErrorKind) -> &str {
+    match kind {
+        ValidationErrorKind::AdditionalItems { .. } => "additionalItems",
+        ValidationErrorKind::AdditionalProperties { .. } => "additionalProperties",
+        ValidationErrorKind::AnyOf { .. } => "anyOf",
+        ValidationErrorKind::BacktrackLimitExceeded { .. }
+        | ValidationErrorKind::Pattern { .. } => "pattern",
+        ValidationErrorKind::Constant { .. } => "const",
+        ValidationErrorKind::Contains => "contains",
+        ValidationErrorKind::ContentEncoding { .. } | ValidationErrorKind::FromUtf8 { .. } => {
+            "contentEncoding"
+        }
+        ValidationErrorKind::ContentMediaType { .. } => "contentMediaType",
+        ValidationErrorKind::Custom { keyword, .. } => keyword,
+        ValidationErrorKind::Enum { .. } => "enum",
+        ValidationErrorKind::ExclusiveMaximum { .. } => "exclusiveMaximum",
+        ValidationErrorKind::ExclusiveMinimum { .. } => "exclusiveMinimum",
+        ValidationErrorKind::FalseSchema => "falseSchema",
+        ValidationErrorKind::Format { .. } => "format",
+        ValidationErrorKind::MaxItems { .. } => "maxItems",
+        ValidationErrorKind::Maximum { .. } => "maximum",
+        ValidationErrorKind::MaxLength { .. } => "maxLength",
+        ValidationErrorKind::MaxProperties { .. } => "maxProperties",
+        ValidationErrorKind::MinItems { .. } => "minItems",
+        ValidationErrorKind::Minimum { .. } => "minimum",
+        ValidationErrorKind::MinLength { .. } => "minLength",
+        ValidationErrorKind::MinProperties { .. } => "minProperties",
+        ValidationErrorKind::MultipleOf { .. } => "multipleOf",
+        ValidationErrorKind::Not { .. } => "not",
+        ValidationErrorKind::OneOfMultipleValid { .. }
+        | ValidationErrorKind::OneOfNotValid { .. } => "oneOf",
+        ValidationErrorKind::PropertyNames { .. } => "propertyNames",
+        ValidationErrorKind::Required { .. } => "required",
+        ValidationErrorKind::Type { .. } => "type",
+        ValidationErrorKind::UnevaluatedItems { .. } => "unevaluatedItems",
+        ValidationErrorKind::UnevaluatedProperties { .. } => "unevaluatedProperties",
+        ValidationErrorKind::UniqueItems => "uniqueItems",
+        ValidationErrorKind::Referencing(_) => "$ref",
+    }
+}
+
+fn params(kind: &ValidationErrorKind) -> Value {
+    match kind {
+        ValidationErrorKind::AdditionalItems { limit } => json!({ "limit": limit }),
+        ValidationErrorKind::AdditionalProperties { unexpected }
+        | ValidationErrorKind::UnevaluatedItems { unexpected }
+        | ValidationErrorKind::UnevaluatedProperties { unexpected } => {
+            json!({ "unexpected": unexpected })
+        }
+        ValidationErrorKind::Constant { expected_value } => {
+            json!({ "expectedValue": expected_value })
+        }
+        ValidationErrorKind::ContentEncoding { content_encoding } => {
+            json!({ "contentEncoding": content_encoding })
+        }
+        ValidationErrorKind::ContentMediaType { content_media_type } => {
+            json!({ "contentMediaType": content_media_type })
+        }
+        ValidationErrorKind::Enum { options } => json!({ "allowedValues": options }),
+        ValidationErrorKind::ExclusiveMaximum { limit }
+        | ValidationErrorKind::ExclusiveMinimum { limit }
+        | ValidationErrorKind::Maximum { limit }
+        | ValidationErrorKind::Minimum { limit } => json!({ "limit": limit }),
+        ValidationErrorKind::Format { format } => json!({ "format": format }),
+        ValidationErrorKind::MaxItems { limit }
+        | ValidationErrorKind::MaxLength { limit }
+        | ValidationErrorKind::MaxProperties { limit }
+        | ValidationErrorKind::MinItems { limit }
+        | ValidationErrorKind::MinLength { limit }
+        | ValidationErrorKind::MinProperties { limit } => json!({ "limit": limit }),
+        ValidationErrorKind::MultipleOf { multiple_of } => json!({ "multipleOf": multiple_of }),
+        ValidationErrorKind::Pattern { pattern } => json!({ "pattern": pattern }),
+        ValidationErrorKind::Required { property } => json!({ "missingProperty": property }),
+        ValidationErrorKind::Type { kind } => json!({ "expected": expected_types(kind) }),
+        ValidationErrorKind::Custom { message, .. } => json!({ "message": message }),
+        _ => Value::Object(Map::new()),
+    }
+}
+
+fn expected_types(kind: &TypeKind) -> Value {
+    match kind {
+        TypeKind::Single(t) => Value::String(t.to_string()),
+        TypeKind::Multiple(set) => set.iter().map(|t| Value::String(t.to_string())).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::error_details;
+    use crate::keywords::with_custom_keywords;
+    use crate::messages::Language;
+    use serde_json::{json, Value};
+
+    fn details(schema: &Value, instance: &Value) -> Value {
+        let validator = with_custom_keywords(jsonschema::options(), Language::En)
+            .build(schema)
+            .unwrap();
+        let error = validator.iter_errors(instance).next().unwrap();
+
+        Value::Object(error_details(
+            &error,
+            error.instance_path().to_string(),
+            String::new(),
+            error.instance(),
+            "message".to_string(),
+        ))
+    }
+
+    #[test]
+    fn type_keyword() {
+        let schema = json!({"properties": {"sum": {"type": "number"}}});
+        assert_eq!(
+            details(&schema, &json!({"sum": "1"})),
+            json!({
+                "instancePath": "/sum",
+                "instancePath1C": "",
+                "schemaPath": "/properties/sum/type",
+                "keyword": "type",
+                "instance": "1",
+                "message": "message",
+                "params": {"expected": "number"}
+            })
+        );
+
+        let schema = json!({"type": ["string", "null"]});
+        assert_eq!(
+            details(&schema, &json!(1))["params"],
+            json!({"expected": ["null", "string"]})
+        );
+    }
+
+    #[test]
+    fn keyword_params() {
+        let cases = [
+            (
+                json!({"required": ["inn"]}),
+                json!({}),
+                "required",
+                json!({"missingProperty": "inn"}),
+            ),
+            (
+                json!({"minLength": 3}),
+                json!("ab"),
+                "minLength",
+                json!({"limit": 3}),
+            ),
+            (
+                json!({"pattern": "^[0-9]+$"}),
+                json!("x"),
+                "pattern",
+                json!({"pattern": "^[0-9]+$"}),
+            ),
+            (
+                json!({"ruBankAccount": {}}),
+                json!({"account": "40702810438000000001", "bik": "044525225"}),
+                "ruBankAccount",
+                json!({"message": "\"40702810438000000001\" is not a valid account for BIK \"044525225\""}),
+            ),
+        ];
+
+        for (schema, instance, keyword, params) in cases {
+            let details = details(&schema, &instance);
+            assert_eq!(details["keyword"], json!(keyword), "{schema}");
+            assert_eq!(details["params"], params, "{schema}");
+        }
+    }
+}
@@ -8,6 +8,7 @@ pub enum ParamType {
     Uri,
     Json,
     StringOrBlob,
+    Number,
 }
 
 impl Display for ParamType {
@@ -18,6 +19,7 @@ impl Display for ParamType {
             Self::Uri => "uri",
             Self::Json => "json",
             Self::StringOrBlob => "string or binary",
+            Self::Number => "non-negative integer",
         };
         f.write_str(name)
     }
@@ -28,6 +30,8 @@ pub enum JsonSchema1CError {
     // Schema errors
     SchemaCompile(String),
     SchemaNotInstalled,
+    CompiledSchemaNotFound(String),
+    SchemaNotFound(String),
 
     // Schema property errors
     PropertyIdNotFound,
@@ -35,14 +39,30 @@ pub enum JsonSchema1CError {
 
     // Parameter errors
     ParamNotFound(usize),
-    ParamConvert { index: usize, expected: ParamType },
+    ParamConvert {
+        index: usize,
+        expected: ParamType,
+    },
     PropertyConvert(ParamType),
 
+    // File errors
+    FileRead {
+        path: String,
+        error: std::io::Error,
+    },
+    FileParse {
+        path: String,
+        error: serde_json::Error,
+    },
+
     // Other errors
     JsonParse(serde_json::Error),
     InvalidUri(String),
+    HttpClient(String),
     OutOfMemory,
     UnknownDraft,
+    UnknownOutputFormat(String),
+    UnknownLanguage(String),
 }
 
 impl Error for JsonSchema1CError {}
@@ -52,6 +72,8 @@ impl Display for JsonSchema1CError {
         match self {
             Self::SchemaCompile(msg) => write!(f, "Schema compilation error: {msg}"),
             Self::SchemaNotInstalled => f.write_str("Schema not installed"),
+            Self::SchemaNotFound(uri) => write!(f, "Schema {uri} not found"),
+            Self::CompiledSchemaNotFound(name) => write!(f, "Compiled schema '{name}' not found"),
             Self::PropertyIdNotFound => f.write_str("Property '$id' not found in schema"),
             Self::PropertyIdNotString => f.write_str("Property '$id' is not a string"),
             Self::ParamNotFound(index) => write!(f, "Parameter {index} not found"),
@@ -61,10 +83,15 @@ impl Display for JsonSchema1CError {
             Self::PropertyConvert(expected) => {
                 write!(f, "Cannot convert property to {expected}")
             }
+            Self::FileRead { path, error } => write!(f, "Cannot read file {path}: {error}"),
+            Self::FileParse { path, error } => write!(f, "JSON parse error in {path}: {error}"),
             Self::JsonParse(e) => write!(f, "JSON parse error: {e}"),
             Self::OutOfMemory => f.write_str("Out of memory"),
             Self::InvalidUri(uri) => write!(f, "Invalid URI: {uri}"),
+            Self::HttpClient(msg) => write!(f, "HTTP client error: {msg}"),
             Self::UnknownDraft => f.write_str("Unknown draft"),
+            Self::UnknownOutputFormat(format) => write!(f, "Unknown output format: {format}"),
+            Self::UnknownLanguage(language) => write!(f, "Unknown language: {language}"),
         }
     }
 }
@@ -1,11 +1,30 @@
 type Format = (&'static str, fn(&str) -> bool);
-pub const FORMATS: [Format; 4] = [
+pub const FORMATS: [Format; 17] = [
     ("ru-inn-individual", ru_inn_individual),
     ("ru-inn-legal-entity", ru_inn_legal_entity),
+    ("ru-snils", ru_snils),
+    ("ru-ogrn", ru_ogrn),
+    ("ru-ogrnip", ru_ogrnip),
+    ("ru-kpp", ru_kpp),
+    ("ru-bik", ru_bik),
     ("kz-iin", kz_iin),
+    ("kz-bin", kz_bin),
+    ("by-unp", by_unp),
+    ("uz-inn", uz_inn),
+    ("kg-inn", kg_inn),
     ("local-date-time", local_date_time),
+    ("local-date", is_valid_date),
+    ("local-time", is_valid_time),
+    ("onec-date", onec_date),
+    ("onec-ref", onec_ref),
 ];
 
+/// Empty date of 1C, the default value of an unfilled date attribute.
+pub const ONEC_EMPTY_DATE: &str = "0001-01-01T00:00:00";
+
+/// Empty reference of 1C, the default value of an unfilled reference attribute.
+pub const ONEC_EMPTY_REF: &str = "00000000-0000-0000-0000-000000000000";
+
 fn ru_inn_individual(r: &str) -> bool {
     if r.len() != 12 || r.starts_with("00") {
         return false;
@@ -52,7 +71,110 @@ fn ru_inn_legal_entity(r: &str) -> bool {
     r.chars().nth(9).unwrap().to_digit(10) == Some(checksum % 11 % 10)
 }
 
+fn ru_snils(r: &str) -> bool {
+    let bytes = r.as_bytes();
+
+    // `12345678901` or `123-456-789 01`
+    let digits = match bytes.len() {
+        11 => bytes.to_vec(),
+        14 if bytes[3] == b'-' && bytes[7] == b'-' && bytes[11] == b' ' => {
+            [&bytes[..3], &bytes[4..7], &bytes[8..11], &bytes[12..]].concat()
+        }
+        _ => return false,
+    };
+
+    if !digits.iter().all(u8::is_ascii_digit) {
+        return false;
+    }
+
+    let digits: Vec<u32> = digits.iter().map(|b| u32::from(b - b'0')).collect();
+    let number = digits[..9].iter().fold(0, |acc, d| acc * 10 + d);
+
+    // Numbers up to 001-001-998 were issued without a checksum
+    if number <= 1_001_998 {
+        return true;
+    }
+
+    let checksum: u32 = digits[..9]
+        .iter()
+        .zip((1..=9).rev())
+        .map(|(digit, weight)| digit * weight)
+        .sum();
+
+    // 100 and 101 give the control number 00, larger sums are taken modulo 101
+    digits[9] * 10 + digits[10] == checksum % 101 % 100
+}
+
+fn ru_ogrn(r: &str) -> bool {
+    registration_number(r, 13, 11)
+}
+
+fn ru_ogrnip(r: &str) -> bool {
+    registration_number(r, 15, 13)
+}
+
+fn ru_kpp(r: &str) -> bool {
+    let bytes = r.as_bytes();
+
+    // NNNN - tax office code, PP - reason code, XXX - sequence number
+    bytes.len() == 9
+        && bytes[..4].iter().all(u8::is_ascii_digit)
+        && bytes[4..6]
+            .iter()
+            .all(|b| b.is_ascii_digit() || b.is_ascii_uppercase())
+        && bytes[6..].iter().all(u8::is_ascii_digit)
+}
+
+fn ru_bik(r: &str) -> bool {
+    r.len() == 9 && r.starts_with("04") && r.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Checks that the last digit equals the preceding digits modulo `divisor`, modulo 10.
+fn registration_number(r: &str, len: usize, divisor: u64) -> bool {
+    if r.len() != len || !r.bytes().all(|b| b.is_ascii_digit()) {
+        return false;
+    }
+
+    let (number, control) = r.split_at(len - 1);
+    let Ok(number) = number.parse::<u64>() else {
+        return false;
+    };
+
+    control.parse::<u64>() == Ok(number % divisor % 10)
+}
+
 fn kz_iin(r: &str) -> bool {
+    if !kz_checksum(r) {
+        return false;
+    }
+
+    // YYMMDD birth date, then the century and sex digit
+    let century = match r.as_bytes()[6] {
+        b'1' | b'2' => "18",
+        b'3' | b'4' => "19",
+        b'5' | b'6' => "20",
+        _ => return false,
+    };
+
+    is_valid_date(&format!("{century}{}-{}-{}", &r[..2], &r[2..4], &r[4..6]))
+}
+
+fn kz_bin(r: &str) -> bool {
+    if !kz_checksum(r) {
+        return false;
+    }
+
+    let bytes = r.as_bytes();
+
+    // YYMM registration date, legal entity type (resident, non-resident, joint venture)
+    // and subdivision kind (head office, branch, representative office, peasant farm)
+    parse_two_digits(&bytes[2..4]).is_some_and(|month| (1..=12).contains(&month))
+        && (b'4'..=b'6').contains(&bytes[4])
+        && (b'0'..=b'3').contains(&bytes[5])
+}
+
+/// Control digit shared by IIN and BIN.
+fn kz_checksum(r: &str) -> bool {
     let first_symbol = r.chars().next();
 
     if r.len() != 12 || r.chars().all(|ch| first_symbol == Some(ch)) {
@@ -93,6 +215,71 @@ fn kz_iin(r: &str) -> bool {
     r.chars().nth(11).unwrap().to_digit(10) == Some(control_value)
 }
 
+fn by_unp(r: &str) -> bool {
+    const LETTERS: &str = "ABCEHKMOPT";
+
+    // Cyrillic letters of the same shape are used interchangeably with Latin ones
+    let chars: Vec<char> = r
+        .chars()
+        .map(|ch| match "АВСЕНКМОРТ".chars().position(|c| c == ch) {
+            Some(i) => LETTERS.as_bytes()[i] as char,
+            None => ch,
+        })
+        .collect();
+
+    if chars.len() != 9 || !chars[2..].iter().all(char::is_ascii_digit) {
+        return false;
+    }
+
+    // Legal entities have a digit prefix, individual entrepreneurs a letter one
+    let (first, second) = if chars[..2].iter().all(char::is_ascii_digit) {
+        (chars[0].to_digit(10), chars[1].to_digit(10))
+    } else {
+        (
+            chars[0].to_digit(36),
+            LETTERS.find(chars[1]).and_then(|i| u32::try_from(i).ok()),
+        )
+    };
+
+    let (Some(first), Some(second)) = (first, second) else {
+        return false;
+    };
+    if !"1234567ABCEHKM".contains(chars[0]) {
+        return false;
+    }
+
+    let checksum: u32 = [first, second]
+        .into_iter()
+        .chain(chars[2..8].iter().filter_map(|ch| ch.to_digit(10)))
+        .zip([29, 23, 19, 17, 13, 7, 5, 3])
+        .map(|(digit, weight)| digit * weight)
+        .sum::<u32>()
+        % 11;
+
+    checksum < 10 && chars[8].to_digit(10) == Some(checksum)
+}
+
+/// Checks the structure only: the tax committee does not publish the rule for the last
+/// digit, so a mistyped digit is not detected.
+fn uz_inn(r: &str) -> bool {
+    // Legal entities start with 2 or 3, individuals with 4 to 8
+    r.len() == 9
+        && r.bytes().all(|b| b.is_ascii_digit())
+        && (b'2'..=b'8').contains(&r.as_bytes()[0])
+}
+
+/// Checks the structure only: the last five digits are a sequence number with no published
+/// check digit, so only the prefix and the date can be verified.
+fn kg_inn(r: &str) -> bool {
+    if r.len() != 14 || !r.bytes().all(|b| b.is_ascii_digit()) {
+        return false;
+    }
+
+    // Organization (0) or sex (1, 2) digit, DDMMYYYY registration or birth date, sequence number
+    (b'0'..=b'2').contains(&r.as_bytes()[0])
+        && is_valid_date(&format!("{}-{}-{}", &r[5..9], &r[3..5], &r[1..3]))
+}
+
 fn local_date_time(datetime: &str) -> bool {
     // Find the position of 'T' or 't' separator
     let Some(t_pos) = datetime.bytes().position(|b| b == b'T' || b == b't') else {
@@ -105,6 +292,161 @@ fn local_date_time(datetime: &str) -> bool {
     is_valid_date(date_part) && is_valid_time(&time_part[1..])
 }
 
+fn onec_date(datetime: &str) -> bool {
+    datetime != ONEC_EMPTY_DATE && onec_date_or_empty(datetime)
+}
+
+/// `onec-date` that also accepts the empty date.
+pub fn onec_date_or_empty(datetime: &str) -> bool {
+    // 1C writes dates without fractional seconds
+    datetime.len() == 19 && local_date_time(datetime)
+}
+
+/// Date values the date range keywords can compare.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DateKind {
+    Date,
+    DateTime,
+    LocalDateTime,
+}
+
+impl DateKind {
+    pub fn from_format(format: &str) -> Option<Self> {
+        match format {
+            "date" | "local-date" => Some(Self::Date),
+            "date-time" => Some(Self::DateTime),
+            "local-date-time" | "onec-date" => Some(Self::LocalDateTime),
+            _ => None,
+        }
+    }
+}
+
+/// Seconds since the Unix epoch and nanoseconds of a date value, `None` if it is malformed.
+/// Values with an offset are converted to UTC, local ones are taken as is.
+pub fn timestamp(value: &str, kind: DateKind) -> Option<(i64, u32)> {
+    let (date, time) = match kind {
+        DateKind::Date => (value, None),
+        DateKind::DateTime | DateKind::LocalDateTime => {
+            let (date, time) = value.split_at(value.find(['T', 't'])?);
+            (date, Some(&time[1..]))
+        }
+    };
+
+    if !is_valid_date(date) {
+        return None;
+    }
+
+    let bytes = date.as_bytes();
+    let days = days_from_civil(
+        parse_four_digits(&bytes[..4])?,
+        parse_two_digits(&bytes[5..7])?,
+        parse_two_digits(&bytes[8..10])?,
+    );
+
+    let Some(time) = time else {
+        return Some((days * 86_400, 0));
+    };
+
+    let (time, offset) = if kind == DateKind::DateTime {
+        split_offset(time)?
+    } else {
+        (time, 0)
+    };
+
+    if !is_valid_time(time) {
+        return None;
+    }
+
+    let bytes = time.as_bytes();
+    let seconds = i64::from(parse_two_digits(&bytes[..2])?) * 3600
+        + i64::from(parse_two_digits(&bytes[3..5])?) * 60
+        + i64::from(parse_two_digits(&bytes[6..8])?);
+
+    // Fractional seconds beyond nanoseconds are dropped
+    let nanos = bytes
+        .get(9..)
+        .unwrap_or_default()
+        .iter()
+        .chain(std::iter::repeat(&b'0'))
+        .take(9)
+        .fold(0, |nanos, digit| nanos * 10 + u32::from(digit - b'0'));
+
+    Some((days * 86_400 + seconds - offset, nanos))
+}
+
+/// Splits `Z` or `+HH:MM` / `-HH:MM` off the time and returns the offset in seconds.
+fn split_offset(time: &str) -> Option<(&str, i64)> {
+    if let Some(time) = time.strip_suffix(['Z', 'z']) {
+        return Some((time, 0));
+    }
+
+    // The value is not checked yet, so the cut may fall inside a multibyte character
+    let (time, offset) = time.split_at_checked(time.len().checked_sub(6)?)?;
+    let bytes = offset.as_bytes();
+
+    let sign = match bytes[0] {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    if bytes[3] != b':' {
+        return None;
+    }
+
+    let hours = parse_two_digits(&bytes[1..3])?;
+    let minutes = parse_two_digits(&bytes[4..6])?;
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+
+    Some((
+        time,
+        sign * (i64::from(hours) * 3600 + i64::from(minutes) * 60),
+    ))
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date.
+fn days_from_civil(year: u16, month: u8, day: u8) -> i64 {
+    let year = i64::from(year) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Reference UUID, bare or as `{type}:{uuid}`.
+fn onec_ref(r: &str) -> bool {
+    let uuid = match r.rsplit_once(':') {
+        Some((type_name, uuid)) => {
+            if type_name.is_empty()
+                || !type_name
+                    .chars()
+                    .all(|ch| ch.is_alphanumeric() || matches!(ch, '.' | '_' | '-'))
+            {
+                return false;
+            }
+            uuid
+        }
+        None => r,
+    };
+
+    let bytes = uuid.as_bytes();
+
+    // 8-4-4-4-12 hexadecimal digits
+    bytes.len() == 36
+        && bytes.iter().enumerate().all(|(i, b)| match i {
+            8 | 13 | 18 | 23 => *b == b'-',
+            _ => b.is_ascii_hexdigit(),
+        })
+}
+
+/// `onec-ref` that rejects the empty reference.
+pub fn onec_ref_not_empty(r: &str) -> bool {
+    onec_ref(r) && !r.ends_with(ONEC_EMPTY_REF)
+}
+
 fn is_valid_date(date: &str) -> bool {
     if date.len() != 10 {
         return false;
@@ -237,7 +579,10 @@ fn parse_four_digits(bytes: &[u8]) -> Option<u16> {
 
 #[cfg(test)]
 mod tests {
-    use crate::formats::{kz_iin, ru_inn_individual};
+    use crate::formats::{
+        by_unp, kg_inn, kz_bin, kz_iin, ru_bik, ru_inn_individual, ru_kpp, ru_ogrn, ru_ogrnip,
+        ru_snils, uz_inn,
+    };
 
     use super::ru_inn_legal_entity;
 
@@ -339,6 +684,143 @@ mod tests {
         );
     }
 
+    #[test]
+    fn valid_snils() {
+        let snils = [
+            "11223344595",
+            "112-233-445 95",
+            "087-654-303 00",
+            "12345678964",
+            "200-000-000 18",
+            "99999999901",
+            "001-001-998 00", // Not checked
+        ];
+
+        for el in snils {
+            assert!(ru_snils(el), "Valid SNILS test failed for: {el}");
+        }
+    }
+
+    #[test]
+    fn invalid_snils() {
+        let snils = [
+            "123",
+            "11223344594",
+            "112-233-445 96",
+            "112 233 445 95",
+            "112-233-44595",
+            "1122334459A",
+            "001-001-999 00",
+            "",
+        ];
+
+        for el in snils {
+            assert!(!ru_snils(el), "Invalid SNILS test failed for: {el}");
+        }
+    }
+
+    #[test]
+    fn valid_ogrn() {
+        let ogrn = [
+            "1027700132195",
+            "1037739010891",
+            "1027700067328",
+            "5077746887312",
+            "1177746126040",
+        ];
+
+        for el in ogrn {
+            assert!(ru_ogrn(el), "Valid OGRN test failed for: {el}");
+        }
+    }
+
+    #[test]
+    fn invalid_ogrn() {
+        let ogrn = [
+            "123",
+            "1027700132194",
+            "102770013219A",
+            "304500116000157",
+            "",
+        ];
+
+        for el in ogrn {
+            assert!(!ru_ogrn(el), "Invalid OGRN test failed for: {el}");
+        }
+    }
+
+    #[test]
+    fn valid_ogrnip() {
+        let ogrnip = [
+            "304500116000157",
+            "316861700133226",
+            "304770000123453",
+            "318502700012349",
+        ];
+
+        for el in ogrnip {
+            assert!(ru_ogrnip(el), "Valid OGRNIP test failed for: {el}");
+        }
+    }
+
+    #[test]
+    fn invalid_ogrnip() {
+        let ogrnip = [
+            "123",
+            "304500116000158",
+            "30450011600015A",
+            "1027700132195",
+            "+30450011600015",
+        ];
+
+        for el in ogrnip {
+            assert!(!ru_ogrnip(el), "Invalid OGRNIP test failed for: {el}");
+        }
+    }
+
+    #[test]
+    fn valid_kpp() {
+        let kpp = ["773601001", "7736AB001", "5003Z1001", "500301001"];
+
+        for el in kpp {
+            assert!(ru_kpp(el), "Valid KPP test failed for: {el}");
+        }
+    }
+
+    #[test]
+    fn invalid_kpp() {
+        let kpp = [
+            "123",
+            "7736ab001",
+            "A73601001",
+            "77360100A",
+            "7736010011",
+            "7736ЯЯ001",
+        ];
+
+        for el in kpp {
+            assert!(!ru_kpp(el), "Invalid KPP test failed for: {el}");
+        }
+    }
+
+    #[test]
+    fn valid_bik() {
+        let bik = ["044525225", "044030653", "040349602"];
+
+        for el in bik {
+            assert!(ru_bik(el), "Valid BIK test failed for: {el}");
+        }
+    }
+
+    #[test]
+    fn invalid_bik() {
+        let bik = ["123", "144525225", "04452522A", "0445252250", "004525225"];
+
+        for el in bik {
+            assert!(!ru_bik(el), "Invalid BIK test failed for: {el}");
+        }
+    }
+
     #[test]
     fn valid_kz_iin() {
         let inn = [
@@ -358,9 +840,121 @@ mod tests {
 
     #[test]
     fn invalid_kz_iin() {
-        let inn = ["123", "842101400014", "150105600011"];
+        let inn = [
+            "123",
+            "842101400014",
+            "150105600011",
+            "730703000014", // Unknown century digit
+            "730703700019", // Unknown century digit
+            "730230400019", // February 30
+            "000229400011", // 1900 is not a leap year
+        ];
+        for el in inn {
+            assert!(!kz_iin(el), "Invalid KZ IIN test failed for: {el}");
+        }
+    }
+
+    #[test]
+    fn valid_kz_bin() {
+        let bin = [
+            "971240001315",
+            "940140000385",
+            "000740001307",
+            "050540004455",
+            "070341014503",
+        ];
+
+        for el in bin {
+            assert!(kz_bin(el), "Valid KZ BIN test failed for: {el}");
+        }
+    }
+
+    #[test]
+    fn invalid_kz_bin() {
+        let bin = [
+            "123",
+            "971240001316", // Checksum
+            "181228500010", // IIN
+            "971340001319", // Month 13
+            "971270001319", // Unknown legal entity type
+            "971245001312", // Unknown subdivision kind
+        ];
+
+        for el in bin {
+            assert!(!kz_bin(el), "Invalid KZ BIN test failed for: {el}");
+        }
+    }
+
+    #[test]
+    fn valid_by_unp() {
+        let unp = [
+            "200988541",
+            "101541947",
+            "600122610",
+            "MA1953684",
+            "МА1953684",
+        ];
+
+        for el in unp {
+            assert!(by_unp(el), "Valid BY UNP test failed for: {el}");
+        }
+    }
+
+    #[test]
+    fn invalid_by_unp() {
+        let unp = [
+            "123",
+            "200988542", // Checksum
+            "800988541", // Unknown region
+            "MZ1953684", // Unknown letter
+            "20098854A",
+            "2009885410",
+        ];
+
+        for el in unp {
+            assert!(!by_unp(el), "Invalid BY UNP test failed for: {el}");
+        }
+    }
+
+    #[test]
+    fn valid_uz_inn() {
+        let inn = ["203366731", "302000023", "471234567"];
+
+        for el in inn {
+            assert!(uz_inn(el), "Valid UZ INN test failed for: {el}");
+        }
+    }
+
+    #[test]
+    fn invalid_uz_inn() {
+        let inn = ["123", "103366731", "903366731", "20336673A", "2033667310"];
+
+        for el in inn {
+            assert!(!uz_inn(el), "Invalid UZ INN test failed for: {el}");
+        }
+    }
+
+    #[test]
+    fn valid_kg_inn() {
+        let inn = ["21505198500123", "12902200000456", "00812199510034"];
+
         for el in inn {
-            assert!(!kz_iin(el));
+            assert!(kg_inn(el), "Valid KG INN test failed for: {el}");
+        }
+    }
+
+    #[test]
+    fn invalid_kg_inn() {
+        let inn = [
+            "123",
+            "31505198500123", // Unknown sex digit
+            "21513198500123", // Month 13
+            "12902190000456", // 1900 is not a leap year
+            "2150519850012A",
+        ];
+
+        for el in inn {
+            assert!(!kg_inn(el), "Invalid KG INN test failed for: {el}");
         }
     }
 
@@ -380,4 +974,118 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn local_date_and_time() {
+        for date in ["2024-02-29", "0001-01-01", "1999-12-31"] {
+            assert!(
+                super::is_valid_date(date),
+                "Valid local date test failed for: {date}"
+            );
+        }
+        for date in [
+            "2023-02-29",
+            "2024-1-01",
+            "2024-01-01T00:00:00",
+            "01.01.2024",
+        ] {
+            assert!(
+                !super::is_valid_date(date),
+                "Invalid local date test failed for: {date}"
+            );
+        }
+
+        for time in ["00:00:00", "23:59:59", "12:30:00.5"] {
+            assert!(
+                super::is_valid_time(time),
+                "Valid local time test failed for: {time}"
+            );
+        }
+        for time in ["24:00:00", "12:30", "12:30:00+03:00", "12:30:00."] {
+            assert!(
+                !super::is_valid_time(time),
+                "Invalid local time test failed for: {time}"
+            );
+        }
+    }
+
+    #[test]
+    fn onec_date() {
+        assert!(super::onec_date("2024-02-29T12:00:00"));
+        assert!(!super::onec_date("2024-02-29T12:00:00.123"));
+        assert!(!super::onec_date("2024-02-29"));
+        assert!(!super::onec_date(super::ONEC_EMPTY_DATE));
+        assert!(super::onec_date_or_empty(super::ONEC_EMPTY_DATE));
+    }
+
+    #[test]
+    fn timestamps() {
+        use super::{timestamp, DateKind};
+
+        assert_eq!(timestamp("1970-01-01", DateKind::Date), Some((0, 0)));
+        assert_eq!(
+            timestamp("2000-03-01", DateKind::Date),
+            Some((951_868_800, 0))
+        );
+        assert_eq!(
+            timestamp("1969-12-31T23:59:59.25", DateKind::LocalDateTime),
+            Some((-1, 250_000_000))
+        );
+        assert_eq!(
+            timestamp("2000-03-01T03:00:00+03:00", DateKind::DateTime),
+            timestamp("2000-03-01T00:00:00Z", DateKind::DateTime)
+        );
+        assert_eq!(
+            timestamp("2000-02-29T21:30:00-02:30", DateKind::DateTime),
+            Some((951_868_800, 0))
+        );
+        assert_eq!(timestamp("2000-03-01T00:00:00", DateKind::DateTime), None);
+        assert_eq!(
+            timestamp("2000-03-01T00:00:00Z", DateKind::LocalDateTime),
+            None
+        );
+        assert_eq!(timestamp("2000-02-30", DateKind::Date), None);
+        assert_eq!(
+            timestamp("2020-01-01T12:00:00Ж12345", DateKind::DateTime),
+            None
+        );
+        assert_eq!(
+            timestamp("2020-01-01T12:00:00+0Ж:00", DateKind::DateTime),
+            None
+        );
+    }
+
+    #[test]
+    fn onec_ref() {
+        let valid = [
+            "a1b2c3d4-0000-11ee-8c90-0242ac120002",
+            "A1B2C3D4-0000-11EE-8C90-0242AC120002",
+            "Справочник.Номенклатура:a1b2c3d4-0000-11ee-8c90-0242ac120002",
+            "CatalogRef.Products:00000000-0000-0000-0000-000000000000",
+            super::ONEC_EMPTY_REF,
+        ];
+        for el in valid {
+            assert!(super::onec_ref(el), "Valid 1C ref test failed for: {el}");
+        }
+
+        let invalid = [
+            "a1b2c3d4000011ee8c900242ac120002",
+            "{a1b2c3d4-0000-11ee-8c90-0242ac120002}",
+            "a1b2c3d4-0000-11ee-8c90-0242ac12000g",
+            ":a1b2c3d4-0000-11ee-8c90-0242ac120002",
+            "Catalog Ref:a1b2c3d4-0000-11ee-8c90-0242ac120002",
+            "Catalog:a1b2c3d4-0000-11ee-8c90-0242ac12000",
+        ];
+        for el in invalid {
+            assert!(!super::onec_ref(el), "Invalid 1C ref test failed for: {el}");
+        }
+
+        assert!(super::onec_ref_not_empty(
+            "a1b2c3d4-0000-11ee-8c90-0242ac120002"
+        ));
+        assert!(!super::onec_ref_not_empty(super::ONEC_EMPTY_REF));
+        assert!(!super::onec_ref_not_empty(
+            "CatalogRef.Products:00000000-0000-0000-0000-000000000000"
+        ));
+    }
 }
@@ -1,11 +1,28 @@
+use crate::coercion;
+use crate::custom_messages;
+use crate::defaults;
+use crate::error_details::error_details;
 use crate::errors::JsonSchema1CError;
-use crate::formats::FORMATS;
+use crate::formats::{self, FORMATS};
+use crate::keywords;
+use crate::labels;
+use crate::messages::{self, Language};
+use crate::onec_path::onec_path;
+use crate::output_format::{self, OutputFormat};
+use crate::positions::{Position, Positions};
+use crate::prune;
+use crate::remote_retriever::{RemoteOptions, RemoteRetriever};
 use crate::retrieve_handler::RetrieveHandler;
+use crate::schema_files;
+use crate::schema_walker::{Branches, SchemaWalker, DEFAULT_BASE_URI};
+use crate::severity;
 use crate::tools::{ComponentResult, Method, MethodVariant, Param, ParamMut, Params, Prop};
 use addin1c::{name, str1c, CStr1C, Connection, RawAddin, Variant};
-use jsonschema::Validator;
-use serde_json::Value;
+use jsonschema::{ValidationError, Validator};
+use serde_json::{json, Value};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 const METHODS: &[Method<JsonSchema1C>] = &[
     Method::func(
@@ -27,12 +44,54 @@ const METHODS: &[Method<JsonSchema1C>] = &[
         2,
         JsonSchema1C::validate,
     ),
+    Method::func(
+        name!("ValidateDetailed"),
+        name!("ПроверитьПодробно"),
+        2,
+        JsonSchema1C::validate_detailed,
+    ),
+    Method::func(
+        name!("ValidateOutput"),
+        name!("ПроверитьВФормате"),
+        2,
+        JsonSchema1C::validate_output,
+    ),
+    Method::func(
+        name!("ApplyDefaults"),
+        name!("ЗаполнитьЗначенияПоУмолчанию"),
+        2,
+        JsonSchema1C::apply_defaults,
+    ),
+    Method::func(
+        name!("PruneAdditional"),
+        name!("УдалитьДополнительныеСвойства"),
+        2,
+        JsonSchema1C::prune_additional,
+    ),
+    Method::func(
+        name!("GetCoercedJSON"),
+        name!("ПолучитьПриведенныйJSON"),
+        0,
+        JsonSchema1C::get_coerced_json,
+    ),
     Method::proc(
         name!("AddScheme"),
         name!("ДобавитьСхему"),
         1,
         JsonSchema1C::add_scheme,
     ),
+    Method::proc(
+        name!("AddSchemeFromFile"),
+        name!("ДобавитьСхемуИзФайла"),
+        1,
+        JsonSchema1C::add_scheme_from_file,
+    ),
+    Method::func(
+        name!("AddSchemesFromDirectory"),
+        name!("ДобавитьСхемыИзКаталога"),
+        3,
+        JsonSchema1C::add_schemes_from_directory,
+    ),
     Method::proc(
         name!("DeleteScheme"),
         name!("УдалитьСхему"),
@@ -51,12 +110,24 @@ const METHODS: &[Method<JsonSchema1C>] = &[
         1,
         JsonSchema1C::set_main_schema,
     ),
+    Method::proc(
+        name!("SetMainSchemeById"),
+        name!("УстановитьОсновнуюСхемуПоИдентификатору"),
+        1,
+        JsonSchema1C::set_main_schema_by_id,
+    ),
     Method::func(
         name!("GetValidationError"),
         name!("ПолучитьОшибкиВалидации"),
         0,
         JsonSchema1C::get_validation_errors,
     ),
+    Method::func(
+        name!("GetValidationWarnings"),
+        name!("ПолучитьПредупрежденияВалидации"),
+        0,
+        JsonSchema1C::get_validation_warnings,
+    ),
     Method::proc(
         name!("ClearMainScheme"),
         name!("ОчиститьОсновнуюСхему"),
@@ -75,6 +146,30 @@ const METHODS: &[Method<JsonSchema1C>] = &[
         0,
         JsonSchema1C::get_schemes,
     ),
+    Method::proc(
+        name!("CompileSchema"),
+        name!("СкомпилироватьСхему"),
+        2,
+        JsonSchema1C::compile_schema,
+    ),
+    Method::func(
+        name!("ValidateWith"),
+        name!("ПроверитьПоСхеме"),
+        3,
+        JsonSchema1C::validate_with,
+    ),
+    Method::func(
+        name!("IsValidWith"),
+        name!("ДействителенПоСхеме"),
+        2,
+        JsonSchema1C::check_valid_with,
+    ),
+    Method::proc(
+        name!("DropCompiled"),
+        name!("УдалитьСкомпилированнуюСхему"),
+        1,
+        JsonSchema1C::drop_compiled,
+    ),
 ];
 
 const PROPS: &[Prop<JsonSchema1C>] = &[
@@ -104,26 +199,98 @@ const PROPS: &[Prop<JsonSchema1C>] = &[
         JsonSchema1C::get_check_formats,
         JsonSchema1C::set_check_formats,
     ),
+    Prop::read_write(
+        name!("AllowEmptyDate"),
+        name!("РазрешитьПустуюДату"),
+        JsonSchema1C::get_allow_empty_date,
+        JsonSchema1C::set_allow_empty_date,
+    ),
+    Prop::read_write(
+        name!("RejectEmptyRef"),
+        name!("ЗапретитьПустуюСсылку"),
+        JsonSchema1C::get_reject_empty_ref,
+        JsonSchema1C::set_reject_empty_ref,
+    ),
     Prop::read_write(
         name!("Draft"),
         name!("Стандарт"),
         JsonSchema1C::get_draft,
         JsonSchema1C::set_draft,
     ),
+    Prop::read_write(
+        name!("BaseDirectory"),
+        name!("БазовыйКаталог"),
+        JsonSchema1C::get_base_directory,
+        JsonSchema1C::set_base_directory,
+    ),
+    Prop::read_write(
+        name!("AllowedHosts"),
+        name!("РазрешенныеХосты"),
+        JsonSchema1C::get_allowed_hosts,
+        JsonSchema1C::set_allowed_hosts,
+    ),
+    Prop::read_write(
+        name!("RemoteTimeout"),
+        name!("ТаймаутЗагрузки"),
+        JsonSchema1C::get_remote_timeout,
+        JsonSchema1C::set_remote_timeout,
+    ),
+    Prop::read_write(
+        name!("RemoteMaxSize"),
+        name!("МаксимальныйРазмерЗагрузки"),
+        JsonSchema1C::get_remote_max_size,
+        JsonSchema1C::set_remote_max_size,
+    ),
+    Prop::read_write(
+        name!("CacheDirectory"),
+        name!("КаталогКэша"),
+        JsonSchema1C::get_cache_directory,
+        JsonSchema1C::set_cache_directory,
+    ),
+    Prop::read_write(
+        name!("CoerceTypes"),
+        name!("ПриводитьТипы"),
+        JsonSchema1C::get_coerce_types,
+        JsonSchema1C::set_coerce_types,
+    ),
+    Prop::read_write(
+        name!("SourcePositions"),
+        name!("ПозицииВТексте"),
+        JsonSchema1C::get_source_positions,
+        JsonSchema1C::set_source_positions,
+    ),
+    Prop::read_write(
+        name!("Language"),
+        name!("Язык"),
+        JsonSchema1C::get_language,
+        JsonSchema1C::set_language,
+    ),
 ];
 
 #[derive(Default)]
+#[allow(clippy::struct_excessive_bools)] // Flags back the component's boolean properties
 pub struct JsonSchema1C {
     schema: Option<String>,
     compiled_schema: Option<Validator>,
+    compiled_schemas: HashMap<String, (Validator, SchemaWalker)>,
     output_format: Option<String>,
     use_custom_formats: bool,
     last_error: Option<JsonSchema1CError>,
     schema_store: HashMap<jsonschema::Uri<String>, Value>,
     ignore_unknown_formats: bool,
     check_formats: bool,
+    allow_empty_date: bool,
+    reject_empty_ref: bool,
     last_validation_errors: Option<String>,
+    last_validation_warnings: Option<String>,
     draft: Option<jsonschema::Draft>,
+    language: Language,
+    base_directory: Option<PathBuf>,
+    remote_options: RemoteOptions,
+    schema_walker: Option<SchemaWalker>,
+    coerce_types: bool,
+    source_positions: bool,
+    last_coerced_json: Option<String>,
 }
 
 // PROPS
@@ -150,6 +317,7 @@ impl JsonSchema1C {
         Ok(())
     }
 
+    #[allow(clippy::unused_self)]
     fn get_version(&mut self, val: &mut ParamMut) -> ComponentResult {
         val.set_str1c(str1c!(env!("CARGO_PKG_VERSION")))?;
         Ok(())
@@ -173,6 +341,24 @@ impl JsonSchema1C {
         Ok(())
     }
 
+    fn get_allow_empty_date(&mut self, val: &mut ParamMut) -> ComponentResult {
+        val.set_bool(self.allow_empty_date)
+    }
+
+    fn set_allow_empty_date(&mut self, val: &Param) -> ComponentResult {
+        self.allow_empty_date = val.get_bool()?;
+        Ok(())
+    }
+
+    fn get_reject_empty_ref(&mut self, val: &mut ParamMut) -> ComponentResult {
+        val.set_bool(self.reject_empty_ref)
+    }
+
+    fn set_reject_empty_ref(&mut self, val: &Param) -> ComponentResult {
+        self.reject_empty_ref = val.get_bool()?;
+        Ok(())
+    }
+
     fn get_draft(&mut self, val: &mut ParamMut) -> ComponentResult {
         match self.draft.as_ref() {
             Some(d) => val.set_str1c(match d {
@@ -205,55 +391,294 @@ impl JsonSchema1C {
         self.draft = Some(draft);
         Ok(())
     }
+
+    fn get_base_directory(&mut self, val: &mut ParamMut) -> ComponentResult {
+        match self.base_directory.as_ref() {
+            Some(dir) => val.set_string(dir.display().to_string()),
+            None => val.set_string(""),
+        }
+    }
+
+    fn set_base_directory(&mut self, val: &Param) -> ComponentResult {
+        let dir = val.get_string()?;
+        self.base_directory = if dir.is_empty() {
+            None
+        } else {
+            Some(PathBuf::from(dir))
+        };
+        Ok(())
+    }
+
+    fn get_allowed_hosts(&mut self, val: &mut ParamMut) -> ComponentResult {
+        val.set_string(self.remote_options.allowed_hosts.join(", "))
+    }
+
+    fn set_allowed_hosts(&mut self, val: &Param) -> ComponentResult {
+        self.remote_options.allowed_hosts = val
+            .get_string()?
+            .split([',', ';'])
+            .map(str::trim)
+            .filter(|h| !h.is_empty())
+            .map(ToString::to_string)
+            .collect();
+        Ok(())
+    }
+
+    fn get_remote_timeout(&mut self, val: &mut ParamMut) -> ComponentResult {
+        let secs = self.remote_options.timeout.as_secs();
+        val.set_i32(i32::try_from(secs).unwrap_or(i32::MAX))
+    }
+
+    fn set_remote_timeout(&mut self, val: &Param) -> ComponentResult {
+        self.remote_options.timeout = Duration::from_secs(val.get_u32()?.into());
+        Ok(())
+    }
+
+    fn get_remote_max_size(&mut self, val: &mut ParamMut) -> ComponentResult {
+        let size = self.remote_options.max_size;
+        val.set_i32(i32::try_from(size).unwrap_or(i32::MAX))
+    }
+
+    fn set_remote_max_size(&mut self, val: &Param) -> ComponentResult {
+        self.remote_options.max_size = val.get_u32()?.into();
+        Ok(())
+    }
+
+    fn get_cache_directory(&mut self, val: &mut ParamMut) -> ComponentResult {
+        match self.remote_options.cache_directory.as_ref() {
+            Some(dir) => val.set_string(dir.display().to_string()),
+            None => val.set_string(""),
+        }
+    }
+
+    fn set_cache_directory(&mut self, val: &Param) -> ComponentResult {
+        let dir = val.get_string()?;
+        self.remote_options.cache_directory = if dir.is_empty() {
+            None
+        } else {
+            Some(PathBuf::from(dir))
+        };
+        Ok(())
+    }
+
+    fn get_coerce_types(&mut self, val: &mut ParamMut) -> ComponentResult {
+        val.set_bool(self.coerce_types)
+    }
+
+    fn set_coerce_types(&mut self, val: &Param) -> ComponentResult {
+        self.coerce_types = val.get_bool()?;
+        Ok(())
+    }
+
+    fn get_source_positions(&mut self, val: &mut ParamMut) -> ComponentResult {
+        val.set_bool(self.source_positions)
+    }
+
+    fn set_source_positions(&mut self, val: &Param) -> ComponentResult {
+        self.source_positions = val.get_bool()?;
+        Ok(())
+    }
+
+    fn get_language(&mut self, val: &mut ParamMut) -> ComponentResult {
+        val.set_string(self.language.code())
+    }
+
+    fn set_language(&mut self, val: &Param) -> ComponentResult {
+        let language = val.get_string()?;
+        self.language = language
+            .parse()
+            .map_err(|()| JsonSchema1CError::UnknownLanguage(language))?;
+        Ok(())
+    }
 }
 
 // METHODS
 impl JsonSchema1C {
     fn check_valid(&mut self, params: &mut Params, ret_val: &mut ParamMut) -> ComponentResult {
+        let check_value = self.prepare_value(params.get_json_value(0)?, None)?;
         let schema = self.get_schema_self()?;
-        let check_value = params.get_json_value(0)?;
-        ret_val.set_bool(schema.is_valid(&check_value))
+        let walker = self.get_schema_walker()?;
+        ret_val.set_bool(is_acceptable(schema, walker, &check_value))
     }
 
     fn validate(&mut self, params: &mut Params, ret_val: &mut ParamMut) -> ComponentResult {
+        let (value, source) = self.read_document(params, 0)?;
+        let check_value = self.prepare_value(value, None)?;
         let schema = self.get_schema_self()?;
-        let check_value = params.get_json_value(0)?;
+        let walker = self.get_schema_walker()?;
         let mut result = params.get_mut(1)?;
 
-        let errors: Vec<String> = schema
-            .iter_errors(&check_value)
-            .map(|e| self.format_validate_error(&e))
-            .collect();
+        let (errors, warnings) = split_findings(
+            schema,
+            walker,
+            &check_value,
+            source.as_deref(),
+            |e, positions| self.format_validate_error(e, &check_value, walker, positions),
+        );
 
         let errors_json = serde_json::to_string(&errors)?;
         self.last_validation_errors = Some(errors_json.clone());
+        self.last_validation_warnings = Some(serde_json::to_string(&warnings)?);
 
         result.set_string(errors_json)?;
         ret_val.set_bool(errors.is_empty())
     }
 
-    fn add_scheme(&mut self, params: &mut Params) -> ComponentResult {
-        let schema_value = params.get_json_value(0)?;
+    fn validate_detailed(
+        &mut self,
+        params: &mut Params,
+        ret_val: &mut ParamMut,
+    ) -> ComponentResult {
+        let (value, source) = self.read_document(params, 0)?;
+        let check_value = self.prepare_value(value, None)?;
+        let schema = self.get_schema_self()?;
+        let walker = self.get_schema_walker()?;
+        let mut result = params.get_mut(1)?;
 
-        let schema_uri = schema_value
-            .get("$id")
-            .ok_or(JsonSchema1CError::PropertyIdNotFound)?
-            .as_str()
-            .ok_or(JsonSchema1CError::PropertyIdNotString)?;
+        let (errors, warnings) = split_findings(
+            schema,
+            walker,
+            &check_value,
+            source.as_deref(),
+            |e, positions| {
+                let (path, instance) = keywords::error_target(e, walker);
+                let path_1c = onec_path(&check_value, &path, 1);
+                let position = positions.get(&path);
+                let mut details = error_details(
+                    e,
+                    path,
+                    path_1c,
+                    instance,
+                    self.error_message(e, instance, walker),
+                );
+
+                if let Some(position) = position {
+                    details.insert("line".to_string(), json!(position.line));
+                    details.insert("column".to_string(), json!(position.column));
+                    details.insert("offset".to_string(), json!(position.offset));
+                }
+                Value::Object(details)
+            },
+        );
+
+        let errors_json = serde_json::to_string(&errors)?;
+        self.last_validation_errors = Some(errors_json.clone());
+        self.last_validation_warnings = Some(serde_json::to_string(&warnings)?);
+
+        result.set_string(errors_json)?;
+        ret_val.set_bool(errors.is_empty())
+    }
+
+    fn validate_output(&mut self, params: &mut Params, ret_val: &mut ParamMut) -> ComponentResult {
+        let check_value = self.prepare_value(params.get_json_value(0)?, None)?;
+        let schema = self.get_schema_self()?;
+        let format_name = params.get_string(1)?;
+
+        let format = format_name
+            .parse::<OutputFormat>()
+            .map_err(|()| JsonSchema1CError::UnknownOutputFormat(format_name))?;
+
+        let output = output_format::render(&schema.evaluate(&check_value), &check_value, format)?;
+        ret_val.set_string(serde_json::to_string(&output)?)
+    }
+
+    fn apply_defaults(&mut self, params: &mut Params, ret_val: &mut ParamMut) -> ComponentResult {
+        let (value, source) = self.read_document(params, 0)?;
+        let mut check_value = self.prepare_value(value, None)?;
+        let schema = self.get_schema_self()?;
+        let walker = self.get_schema_walker()?;
+        let mut result = params.get_mut(1)?;
+
+        // Conditional branches are chosen by the document as it was passed in
+        let branches = Branches::new(&schema.evaluate(&check_value))?;
+        defaults::apply_defaults(walker, &branches, &mut check_value);
+
+        let (errors, warnings) = split_findings(
+            schema,
+            walker,
+            &check_value,
+            source.as_deref(),
+            |e, positions| self.format_validate_error(e, &check_value, walker, positions),
+        );
+
+        self.last_validation_errors = Some(serde_json::to_string(&errors)?);
+        self.last_validation_warnings = Some(serde_json::to_string(&warnings)?);
+
+        result.set_string(serde_json::to_string(&check_value)?)?;
+        ret_val.set_bool(errors.is_empty())
+    }
 
-        let uri = jsonschema::Uri::parse(schema_uri.to_string())
-            .map_err(|_| JsonSchema1CError::InvalidUri(schema_uri.to_string()))?;
+    fn prune_additional(&mut self, params: &mut Params, ret_val: &mut ParamMut) -> ComponentResult {
+        let schema = self.get_schema_self()?;
+        let mut check_value = params.get_json_value(0)?;
+        let mut removed = params.get_mut(1)?;
+
+        let pointers = prune::prune_additional(schema, &mut check_value);
 
+        removed.set_string(serde_json::to_string(&pointers)?)?;
+        ret_val.set_string(serde_json::to_string(&check_value)?)
+    }
+
+    fn get_coerced_json(
+        &mut self,
+        _params: &mut Params,
+        ret_val: &mut ParamMut,
+    ) -> ComponentResult {
+        match self.last_coerced_json.as_deref() {
+            Some(json) => ret_val.set_string(json),
+            None => ret_val.set_empty(),
+        }
+    }
+
+    fn add_scheme(&mut self, params: &mut Params) -> ComponentResult {
+        let schema_value = params.get_json_value(0)?;
+        let uri = Self::schema_id(&schema_value)?;
         self.schema_store.insert(uri, schema_value);
         Ok(())
     }
 
+    fn add_scheme_from_file(&mut self, params: &mut Params) -> ComponentResult {
+        let path = PathBuf::from(params.get_string(0)?);
+        self.add_schema_file(&path)?;
+        Ok(())
+    }
+
+    fn add_schemes_from_directory(
+        &mut self,
+        params: &mut Params,
+        ret_val: &mut ParamMut,
+    ) -> ComponentResult {
+        let dir = PathBuf::from(params.get_string(0)?);
+        let mask = params.get_string(1)?;
+        let recursive = params.get_bool(2)?;
+
+        let mut loaded = Vec::new();
+        let mut failed = Vec::new();
+
+        for path in schema_files::find_files(&dir, &mask, recursive)? {
+            match self.add_schema_file(&path) {
+                Ok(uri) => loaded.push(json!({
+                    "file": path.display().to_string(),
+                    "uri": uri.as_str(),
+                })),
+                Err(e) => failed.push(json!({
+                    "file": path.display().to_string(),
+                    "error": e.to_string(),
+                })),
+            }
+        }
+
+        let report = json!({ "loaded": loaded, "failed": failed });
+        ret_val.set_string(serde_json::to_string(&report)?)
+    }
+
     fn delete_scheme(&mut self, params: &mut Params) -> ComponentResult {
         let uri = params.get_uri(0)?;
         self.schema_store.remove(&uri);
         Ok(())
     }
 
+    #[allow(clippy::unnecessary_wraps)]
     fn delete_all_schemes(&mut self, _params: &mut Params) -> ComponentResult {
         self.schema_store.clear();
         Ok(())
@@ -268,27 +693,31 @@ impl JsonSchema1C {
 
     fn set_main_schema(&mut self, params: &mut Params) -> ComponentResult {
         let schema_value = params.get_json_value(0)?;
+        self.install_main_schema(&schema_value)?;
+        Ok(())
+    }
 
-        let mut options = jsonschema::options()
-            .should_ignore_unknown_formats(self.ignore_unknown_formats)
-            .should_validate_formats(self.check_formats);
+    fn set_main_schema_by_id(&mut self, params: &mut Params) -> ComponentResult {
+        let uri = params.get_uri(0)?;
 
-        if self.use_custom_formats {
-            for (name, func) in FORMATS {
-                options = options.with_format(name, func);
-            }
+        let base_uri = uri.strip_fragment();
+        if !self.schema_store.contains_key(base_uri.as_str()) {
+            return Err(JsonSchema1CError::SchemaNotFound(base_uri.to_string()));
         }
 
-        if let Some(d) = self.draft {
-            options = options.with_draft(d);
+        // Compiled through a reference so that the stored schema keeps its own base URI
+        let schema_value = json!({ "$ref": uri.as_str() });
+        self.install_main_schema(&schema_value)?;
+
+        // `Schema` returns the stored (sub)schema rather than the reference to it
+        let walker = self.get_schema_walker()?;
+        let stored = walker
+            .root()
+            .and_then(|root| walker.resolve(&root, uri.as_str()))
+            .map(|scope| scope.schema.to_string());
+        if let Some(stored) = stored {
+            self.schema = Some(stored);
         }
-
-        self.compiled_schema = Some(
-            options
-                .with_retriever(RetrieveHandler::new(self.schema_store.clone()))
-                .build(&schema_value)?,
-        );
-        self.schema = Some(schema_value.to_string());
         Ok(())
     }
 
@@ -303,6 +732,18 @@ impl JsonSchema1C {
         }
     }
 
+    fn get_validation_warnings(
+        &mut self,
+        _params: &mut Params,
+        ret_val: &mut ParamMut,
+    ) -> ComponentResult {
+        match self.last_validation_warnings.as_deref() {
+            Some(w) => ret_val.set_string(w),
+            None => ret_val.set_empty(),
+        }
+    }
+
+    #[allow(clippy::unnecessary_wraps)]
     fn clear_main_schema(&mut self, _params: &mut Params) -> ComponentResult {
         self.schema = None;
         Ok(())
@@ -310,13 +751,58 @@ impl JsonSchema1C {
 
     fn has_scheme(&mut self, params: &mut Params, ret_val: &mut ParamMut) -> ComponentResult {
         let url = params.get_uri(0)?;
-        ret_val.set_bool(self.schema_store.get(&url).is_some())
+        ret_val.set_bool(self.schema_store.contains_key(&url))
     }
 
     fn get_schemes(&mut self, _params: &mut Params, ret_val: &mut ParamMut) -> ComponentResult {
         let result = serde_json::to_string(&self.schema_store)?;
         ret_val.set_string(result)
     }
+
+    fn compile_schema(&mut self, params: &mut Params) -> ComponentResult {
+        let name = params.get_string(0)?;
+        let schema_value = params.get_json_value(1)?;
+        let validator = self.build_validator(&schema_value)?;
+        let walker = self.build_walker(&schema_value)?;
+        self.compiled_schemas.insert(name, (validator, walker));
+        Ok(())
+    }
+
+    fn validate_with(&mut self, params: &mut Params, ret_val: &mut ParamMut) -> ComponentResult {
+        let name = params.get_string(0)?;
+        let (value, source) = self.read_document(params, 1)?;
+        let check_value = self.prepare_value(value, Some(&name))?;
+        let (schema, walker) = self.get_compiled_schema(&name)?;
+        let mut result = params.get_mut(2)?;
+
+        let (errors, warnings) = split_findings(
+            schema,
+            walker,
+            &check_value,
+            source.as_deref(),
+            |e, positions| self.format_validate_error(e, &check_value, walker, positions),
+        );
+
+        let errors_json = serde_json::to_string(&errors)?;
+        self.last_validation_errors = Some(errors_json.clone());
+        self.last_validation_warnings = Some(serde_json::to_string(&warnings)?);
+
+        result.set_string(errors_json)?;
+        ret_val.set_bool(errors.is_empty())
+    }
+
+    fn check_valid_with(&mut self, params: &mut Params, ret_val: &mut ParamMut) -> ComponentResult {
+        let name = params.get_string(0)?;
+        let check_value = self.prepare_value(params.get_json_value(1)?, Some(&name))?;
+        let (schema, walker) = self.get_compiled_schema(&name)?;
+        ret_val.set_bool(is_acceptable(schema, walker, &check_value))
+    }
+
+    fn drop_compiled(&mut self, params: &mut Params) -> ComponentResult {
+        let name = params.get_string(0)?;
+        self.compiled_schemas.remove(&name);
+        Ok(())
+    }
 }
 
 impl JsonSchema1C {
@@ -326,16 +812,198 @@ impl JsonSchema1C {
             .ok_or(JsonSchema1CError::SchemaNotInstalled)
     }
 
-    fn format_validate_error(&self, error: &jsonschema::ValidationError) -> String {
+    fn schema_id(schema: &Value) -> Result<jsonschema::Uri<String>, JsonSchema1CError> {
+        let schema_uri = schema
+            .get("$id")
+            .ok_or(JsonSchema1CError::PropertyIdNotFound)?
+            .as_str()
+            .ok_or(JsonSchema1CError::PropertyIdNotString)?;
+
+        jsonschema::Uri::parse(schema_uri.to_string())
+            .map_err(|_| JsonSchema1CError::InvalidUri(schema_uri.to_string()))
+    }
+
+    /// Registers a schema file by its `$id`, or by its `file://` URI when `$id` is absent.
+    fn add_schema_file(
+        &mut self,
+        path: &Path,
+    ) -> Result<jsonschema::Uri<String>, JsonSchema1CError> {
+        let schema_value = schema_files::read_schema(path)?;
+
+        let uri = if schema_value.get("$id").is_some() {
+            Self::schema_id(&schema_value)?
+        } else {
+            schema_files::file_uri(path)?
+        };
+
+        self.schema_store.insert(uri.clone(), schema_value);
+        Ok(uri)
+    }
+
+    /// Document to validate, with its source text when `SourcePositions` is set.
+    fn read_document(
+        &self,
+        params: &Params,
+        index: usize,
+    ) -> Result<(Value, Option<Vec<u8>>), JsonSchema1CError> {
+        if self.source_positions {
+            let (value, source) = params.get_json_with_source(index)?;
+            Ok((value, Some(source)))
+        } else {
+            Ok((params.get_json_value(index)?, None))
+        }
+    }
+
+    /// Applies `CoerceTypes` to a document checked against the main schema, or against
+    /// the compiled schema `name`.
+    fn prepare_value(
+        &mut self,
+        value: Value,
+        name: Option<&str>,
+    ) -> Result<Value, JsonSchema1CError> {
+        self.last_coerced_json = None;
+        if !self.coerce_types {
+            return Ok(value);
+        }
+
+        let (schema, walker) = match name {
+            Some(name) => {
+                let (schema, walker) = self.get_compiled_schema(name)?;
+                (schema, walker)
+            }
+            None => (self.get_schema_self()?, self.get_schema_walker()?),
+        };
+        let value = coerce_document(schema, walker, value)?;
+
+        self.last_coerced_json = Some(serde_json::to_string(&value)?);
+        Ok(value)
+    }
+
+    fn get_schema_walker(&self) -> Result<&SchemaWalker, JsonSchema1CError> {
+        self.schema_walker
+            .as_ref()
+            .ok_or(JsonSchema1CError::SchemaNotInstalled)
+    }
+
+    fn install_main_schema(&mut self, schema_value: &Value) -> Result<(), JsonSchema1CError> {
+        let validator = self.build_validator(schema_value)?;
+        let walker = self.build_walker(schema_value)?;
+
+        self.compiled_schema = Some(validator);
+        self.schema_walker = Some(walker);
+        self.schema = Some(schema_value.to_string());
+        Ok(())
+    }
+
+    /// Walker over a schema built by `build_validator`, which loads the schemas it references.
+    fn build_walker(&self, schema: &Value) -> Result<SchemaWalker, JsonSchema1CError> {
+        SchemaWalker::new(schema, &self.base_uri()?, &self.schema_store, self.draft)
+    }
+
+    /// Base URI of schemas without `$id`, the same one the validator uses.
+    fn base_uri(&self) -> Result<String, JsonSchema1CError> {
+        match self.base_directory.as_ref() {
+            Some(dir) => Ok(format!("{}/", schema_files::file_uri(dir)?)),
+            None => Ok(DEFAULT_BASE_URI.to_string()),
+        }
+    }
+
+    fn get_compiled_schema(
+        &self,
+        name: &str,
+    ) -> Result<&(Validator, SchemaWalker), JsonSchema1CError> {
+        self.compiled_schemas
+            .get(name)
+            .ok_or_else(|| JsonSchema1CError::CompiledSchemaNotFound(name.to_string()))
+    }
+
+    fn build_validator(&mut self, schema: &Value) -> Result<Validator, JsonSchema1CError> {
+        let mut options = jsonschema::options()
+            .should_ignore_unknown_formats(self.ignore_unknown_formats)
+            .should_validate_formats(self.check_formats);
+
+        if self.use_custom_formats {
+            for (name, func) in FORMATS {
+                options = options.with_format(name, func);
+            }
+
+            if self.allow_empty_date {
+                options = options.with_format("onec-date", formats::onec_date_or_empty);
+            }
+
+            if self.reject_empty_ref {
+                options = options.with_format("onec-ref", formats::onec_ref_not_empty);
+            }
+        }
+
+        if let Some(d) = self.draft {
+            options = options.with_draft(d);
+        }
+
+        options = keywords::with_custom_keywords(options, self.language);
+
+        let mut retriever = RetrieveHandler::new(self.schema_store.clone());
+
+        if let Some(dir) = self.base_directory.as_ref() {
+            options = options.with_base_uri(self.base_uri()?);
+            retriever = retriever.with_file_root(dir.clone());
+        }
+
+        if self.remote_options.is_enabled() {
+            retriever = retriever.with_remote(RemoteRetriever::new(self.remote_options.clone())?);
+        }
+
+        let validator = options.with_retriever(retriever.clone()).build(schema)?;
+
+        // Schemas read from disk or network are kept so they are not loaded again
+        self.schema_store.extend(retriever.take_retrieved());
+        Ok(validator)
+    }
+
+    fn format_validate_error(
+        &self,
+        error: &jsonschema::ValidationError,
+        root: &Value,
+        walker: &SchemaWalker,
+        positions: &Positions,
+    ) -> String {
+        let (path, instance) = keywords::error_target(error, walker);
         match &self.output_format {
-            Some(fmt) => fmt
-                .replace("{path}", &error.instance_path().to_string())
-                .replace("{instance}", &error.instance().to_string())
-                .replace("{schema_path}", &error.schema_path().to_string())
-                .replace("{error}", &error.to_string()),
-            None => error.to_string(),
+            Some(fmt) => {
+                let labels = labels::labels(walker, error);
+                let position = positions.get(&path);
+                let place = |field: fn(Position) -> usize| {
+                    position
+                        .map(field)
+                        .map(|n| n.to_string())
+                        .unwrap_or_default()
+                };
+                fmt.replace("{title}", labels.title.unwrap_or_default())
+                    .replace("{description}", labels.description.unwrap_or_default())
+                    .replace("{line}", &place(|p| p.line))
+                    .replace("{column}", &place(|p| p.column))
+                    .replace("{offset}", &place(|p| p.offset))
+                    .replace("{path_1c}", &onec_path(root, &path, 1))
+                    .replace("{path_1c_0}", &onec_path(root, &path, 0))
+                    .replace("{path}", &path)
+                    .replace("{instance}", &instance.to_string())
+                    .replace("{schema_path}", &error.schema_path().to_string())
+                    .replace("{error}", &self.error_message(error, instance, walker))
+            }
+            None => self.error_message(error, instance, walker),
         }
     }
+
+    /// Message from `errorMessage` in the schema, or the standard one.
+    fn error_message(
+        &self,
+        error: &jsonschema::ValidationError,
+        instance: &Value,
+        walker: &SchemaWalker,
+    ) -> String {
+        custom_messages::custom_message(walker, error, instance)
+            .unwrap_or_else(|| messages::message(error, self.language))
+    }
 }
 
 impl RawAddin for JsonSchema1C {
@@ -498,3 +1166,72 @@ impl RawAddin for JsonSchema1C {
         }
     }
 }
+
+/// Document with `CoerceTypes` applied by the rules of `validator`.
+fn coerce_document(
+    validator: &Validator,
+    walker: &SchemaWalker,
+    mut value: Value,
+) -> Result<Value, JsonSchema1CError> {
+    let branches = Branches::new(&validator.evaluate(&value))?;
+    if coercion::coerce_types(walker, &branches, &mut value) {
+        // Coerced values may select other `then`/`else` branches
+        let branches = Branches::new(&validator.evaluate(&value))?;
+        coercion::coerce_types(walker, &branches, &mut value);
+    }
+
+    Ok(value)
+}
+
+/// Errors of `instance` rendered by `render`, with `x-severity: warning` findings kept apart
+/// and errors sharing a string `errorMessage` reported once. The document is validated once; with its `source` text the failing values are located
+/// from the same errors.
+fn split_findings<T>(
+    validator: &Validator,
+    walker: &SchemaWalker,
+    instance: &Value,
+    source: Option<&[u8]>,
+    render: impl Fn(&ValidationError, &Positions) -> T,
+) -> (Vec<T>, Vec<T>) {
+    let found = custom_messages::merge_declared(walker, validator.iter_errors(instance).collect());
+    let positions = find_positions(walker, &found, source);
+
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+
+    for error in &found {
+        if severity::is_warning(walker, error) {
+            warnings.push(render(error, &positions));
+        } else {
+            errors.push(render(error, &positions));
+        }
+    }
+
+    (errors, warnings)
+}
+
+/// Positions of the failing values in the source text, none without it.
+fn find_positions(
+    walker: &SchemaWalker,
+    errors: &[ValidationError],
+    source: Option<&[u8]>,
+) -> Positions {
+    let Some(source) = source else {
+        return Positions::default();
+    };
+
+    let paths: Vec<String> = errors
+        .iter()
+        .map(|error| keywords::error_target(error, walker).0)
+        .collect();
+
+    Positions::find(source, paths.iter().map(String::as_str))
+}
+
+/// Valid, or failing only rules marked `x-severity: warning`.
+fn is_acceptable(validator: &Validator, walker: &SchemaWalker, instance: &Value) -> bool {
+    validator.is_valid(instance)
+        || validator
+            .iter_errors(instance)
+            .all(|error| severity::is_warning(walker, &error))
+}
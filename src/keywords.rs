@@ -0,0 +1,482 @@
+use crate::formats::{timestamp, DateKind};
+use crate::labels::{evaluation_tokens, keyword_position};
+use crate::messages::Language;
+use crate::schema_walker::{pointer_push, SchemaWalker};
+use jsonschema::error::ValidationErrorKind;
+use jsonschema::paths::Location;
+use jsonschema::{Keyword, ValidationError, ValidationOptions};
+use serde_json::{Map, Value};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const BANK_ACCOUNT: &str = "ruBankAccount";
+pub const FORMAT_MINIMUM: &str = "formatMinimum";
+pub const FORMAT_MAXIMUM: &str = "formatMaximum";
+pub const NOT_IN_FUTURE: &str = "notInFuture";
+
+/// Largest UTC offset in use, values without an offset may be ahead of UTC by up to it.
+const MAX_UTC_OFFSET: i64 = 14 * 3600;
+
+const DEFAULT_ACCOUNT_PROPERTY: &str = "account";
+const DEFAULT_BIK_PROPERTY: &str = "bik";
+
+/// Registers the component's own schema keywords.
+pub fn with_custom_keywords(options: ValidationOptions, language: Language) -> ValidationOptions {
+    options
+        .with_keyword(
+            BANK_ACCOUNT,
+            move |_parent: &Map<String, Value>, value: &Value, _location: Location| {
+                let keyword = BankAccount::new(value, language)?;
+                Ok(Box::new(keyword) as Box<dyn Keyword>)
+            },
+        )
+        .with_keyword(
+            FORMAT_MINIMUM,
+            move |parent: &Map<String, Value>, value: &Value, _location: Location| {
+                let keyword = DateLimit::new(FORMAT_MINIMUM, parent, value, language)?;
+                Ok(date_keyword(keyword))
+            },
+        )
+        .with_keyword(
+            FORMAT_MAXIMUM,
+            move |parent: &Map<String, Value>, value: &Value, _location: Location| {
+                let keyword = DateLimit::new(FORMAT_MAXIMUM, parent, value, language)?;
+                Ok(date_keyword(keyword))
+            },
+        )
+        .with_keyword(
+            NOT_IN_FUTURE,
+            move |parent: &Map<String, Value>, value: &Value, _location: Location| {
+                let keyword = NotInFuture::new(parent, value, language)?;
+                Ok(date_keyword(keyword))
+            },
+        )
+}
+
+/// Instance path and value an error refers to.
+///
+/// `ruBankAccount` validates the object holding both fields, while its errors are reported
+/// at the account field named in the failing keyword.
+pub fn error_target<'a>(error: &'a ValidationError, walker: &SchemaWalker) -> (String, &'a Value) {
+    let path = error.instance_path().as_str();
+    let instance = error.instance().as_ref();
+
+    if let ValidationErrorKind::Custom { keyword, .. } = error.kind() {
+        if keyword == BANK_ACCOUNT {
+            let property = account_property(walker, error);
+            if let Some(value) = instance.get(&property) {
+                return (pointer_push(path, &property), value);
+            }
+        }
+    }
+
+    (path.to_string(), instance)
+}
+
+/// `accountProperty` of the `ruBankAccount` keyword that raised the error.
+fn account_property(walker: &SchemaWalker, error: &ValidationError) -> String {
+    let tokens = evaluation_tokens(error);
+    let position = keyword_position(error, &tokens);
+
+    walker
+        .at_evaluation_path(&tokens[..=position].join("/"))
+        .and_then(|keyword| keyword.schema.get("accountProperty")?.as_str())
+        .unwrap_or(DEFAULT_ACCOUNT_PROPERTY)
+        .to_string()
+}
+
+/// `ruBankAccount: {"accountProperty": "account", "bikProperty": "bik"}` - checks the
+/// settlement account against the BIK with the Central Bank control key.
+struct BankAccount {
+    account_property: String,
+    bik_property: String,
+    language: Language,
+}
+
+impl BankAccount {
+    fn new(value: &Value, language: Language) -> Result<Self, ValidationError<'static>> {
+        let Value::Object(config) = value else {
+            return Err(ValidationError::schema(format!(
+                "'{BANK_ACCOUNT}' must be an object"
+            )));
+        };
+
+        let property = |name: &str, default: &str| match config.get(name) {
+            None => Ok(default.to_string()),
+            Some(Value::String(property)) => Ok(property.clone()),
+            Some(_) => Err(ValidationError::schema(format!(
+                "'{BANK_ACCOUNT}.{name}' must be a string"
+            ))),
+        };
+
+        Ok(Self {
+            account_property: property("accountProperty", DEFAULT_ACCOUNT_PROPERTY)?,
+            bik_property: property("bikProperty", DEFAULT_BIK_PROPERTY)?,
+            language,
+        })
+    }
+
+    /// Account and BIK when both are present as strings; other keywords report missing fields.
+    fn fields<'a>(&self, instance: &'a Value) -> Option<(&'a str, &'a str)> {
+        let account = instance.get(&self.account_property)?.as_str()?;
+        let bik = instance.get(&self.bik_property)?.as_str()?;
+        Some((account, bik))
+    }
+}
+
+impl Keyword for BankAccount {
+    fn validate<'i>(&self, instance: &'i Value) -> Result<(), ValidationError<'i>> {
+        match self.fields(instance) {
+            Some((account, bik)) if !is_valid_account(account, bik) => {
+                Err(ValidationError::custom(match self.language {
+                    Language::En => {
+                        format!("\"{account}\" is not a valid account for BIK \"{bik}\"")
+                    }
+                    Language::Ru => {
+                        format!("Счет \"{account}\" не соответствует БИК \"{bik}\"")
+                    }
+                }))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn is_valid(&self, instance: &Value) -> bool {
+        self.fields(instance)
+            .is_none_or(|(account, bik)| is_valid_account(account, bik))
+    }
+}
+
+/// Kind of dates compared by a date range keyword, taken from `format` next to it.
+fn date_kind(parent: &Map<String, Value>) -> Option<DateKind> {
+    parent
+        .get("format")
+        .and_then(Value::as_str)
+        .and_then(DateKind::from_format)
+}
+
+/// Date range keyword, or one checking nothing when `format` next to it is absent or not
+/// a date, as ajv-formats does with formats it cannot compare.
+fn date_keyword(keyword: Option<impl Keyword + 'static>) -> Box<dyn Keyword> {
+    match keyword {
+        Some(keyword) => Box::new(keyword),
+        None => Box::new(Unchecked),
+    }
+}
+
+struct Unchecked;
+
+impl Keyword for Unchecked {
+    fn validate<'i>(&self, _instance: &'i Value) -> Result<(), ValidationError<'i>> {
+        Ok(())
+    }
+
+    fn is_valid(&self, _instance: &Value) -> bool {
+        true
+    }
+}
+
+/// `formatMinimum` / `formatMaximum: "2000-01-01"` - inclusive bound of a date string
+/// in the format given by `format`.
+struct DateLimit {
+    maximum: bool,
+    kind: DateKind,
+    limit: (i64, u32),
+    limit_text: String,
+    language: Language,
+}
+
+impl DateLimit {
+    fn new(
+        keyword: &str,
+        parent: &Map<String, Value>,
+        value: &Value,
+        language: Language,
+    ) -> Result<Option<Self>, ValidationError<'static>> {
+        let malformed = || {
+            ValidationError::schema(format!(
+                "'{keyword}' must be a string in the format of the schema"
+            ))
+        };
+
+        let text = value.as_str().ok_or_else(malformed)?;
+        let Some(kind) = date_kind(parent) else {
+            return Ok(None);
+        };
+        let limit = timestamp(text, kind)
+            .map(|limit| (limit, text))
+            .ok_or_else(malformed)?;
+
+        Ok(Some(Self {
+            maximum: keyword == FORMAT_MAXIMUM,
+            kind,
+            limit: limit.0,
+            limit_text: limit.1.to_string(),
+            language,
+        }))
+    }
+
+    /// Malformed values and non-strings are left to `format` and `type`.
+    fn is_within(&self, instance: &Value) -> bool {
+        let Some(value) = instance
+            .as_str()
+            .and_then(|text| timestamp(text, self.kind))
+        else {
+            return true;
+        };
+
+        if self.maximum {
+            value <= self.limit
+        } else {
+            value >= self.limit
+        }
+    }
+}
+
+impl Keyword for DateLimit {
+    fn validate<'i>(&self, instance: &'i Value) -> Result<(), ValidationError<'i>> {
+        if self.is_within(instance) {
+            return Ok(());
+        }
+
+        let limit = &self.limit_text;
+        Err(ValidationError::custom(
+            match (self.language, self.maximum) {
+                (Language::En, false) => format!("{instance} is earlier than \"{limit}\""),
+                (Language::En, true) => format!("{instance} is later than \"{limit}\""),
+                (Language::Ru, false) => format!("Дата {instance} раньше \"{limit}\""),
+                (Language::Ru, true) => format!("Дата {instance} позже \"{limit}\""),
+            },
+        ))
+    }
+
+    fn is_valid(&self, instance: &Value) -> bool {
+        self.is_within(instance)
+    }
+}
+
+/// `notInFuture: true` - the date string is not later than the current moment.
+struct NotInFuture {
+    kind: DateKind,
+    enabled: bool,
+    language: Language,
+}
+
+impl NotInFuture {
+    fn new(
+        parent: &Map<String, Value>,
+        value: &Value,
+        language: Language,
+    ) -> Result<Option<Self>, ValidationError<'static>> {
+        let Value::Bool(enabled) = value else {
+            return Err(ValidationError::schema(format!(
+                "'{NOT_IN_FUTURE}' must be a boolean"
+            )));
+        };
+
+        Ok(date_kind(parent).map(|kind| Self {
+            kind,
+            enabled: *enabled,
+            language,
+        }))
+    }
+
+    fn is_past(&self, instance: &Value) -> bool {
+        let value = instance
+            .as_str()
+            .and_then(|text| timestamp(text, self.kind));
+        let Some(value) = value.filter(|_| self.enabled) else {
+            return true;
+        };
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| {
+                i64::try_from(elapsed.as_secs()).unwrap_or(i64::MAX)
+            });
+
+        // The time zone of local values is unknown, so any of them is accepted
+        let now = match self.kind {
+            DateKind::DateTime => now,
+            DateKind::Date | DateKind::LocalDateTime => now.saturating_add(MAX_UTC_OFFSET),
+        };
+
+        value < (now.saturating_add(1), 0)
+    }
+}
+
+impl Keyword for NotInFuture {
+    fn validate<'i>(&self, instance: &'i Value) -> Result<(), ValidationError<'i>> {
+        if self.is_past(instance) {
+            return Ok(());
+        }
+
+        Err(ValidationError::custom(match self.language {
+            Language::En => format!("{instance} is in the future"),
+            Language::Ru => format!("Дата {instance} находится в будущем"),
+        }))
+    }
+
+    fn is_valid(&self, instance: &Value) -> bool {
+        self.is_past(instance)
+    }
+}
+
+/// Control key check of a 20-digit account. BIKs of a malformed shape are left to `ru-bik`.
+fn is_valid_account(account: &str, bik: &str) -> bool {
+    if bik.len() != 9 || !bik.bytes().all(|b| b.is_ascii_digit()) {
+        return true;
+    }
+
+    if account.len() != 20 || !account.bytes().all(|b| b.is_ascii_digit()) {
+        return false;
+    }
+
+    // Accounts opened in cash settlement centres are keyed by "0" + the 5th and 6th BIK digits
+    let prefix = match &bik[6..] {
+        "000" | "001" | "002" => format!("0{}", &bik[4..6]),
+        code => code.to_string(),
+    };
+
+    let checksum: u32 = prefix
+        .bytes()
+        .chain(account.bytes())
+        .zip([7, 1, 3].into_iter().cycle())
+        .map(|(digit, weight)| u32::from(digit - b'0') * weight)
+        .sum();
+
+    checksum.is_multiple_of(10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{error_target, is_valid_account, with_custom_keywords};
+    use crate::messages::Language;
+    use crate::schema_walker::testing::walker;
+    use serde_json::json;
+
+    #[test]
+    fn account_control_key() {
+        assert!(is_valid_account("40702810938000000001", "044525225"));
+        assert!(is_valid_account("40817810899910004312", "044525225"));
+        assert!(is_valid_account("40102810245370000002", "044525000"));
+        assert!(!is_valid_account("40702810438000000001", "044525225"));
+        assert!(!is_valid_account("4070281093800000000", "044525225"));
+        assert!(!is_valid_account("40702810938000000001", "044525974"));
+    }
+
+    #[test]
+    fn error_points_at_account() {
+        // Schemas sharing the keyword location must not share the account property
+        for (property, payment) in [("rs", "payment"), ("acc", "payment"), ("account", "p")] {
+            let mut keyword = json!({"bikProperty": "bik"});
+            if property != "account" {
+                keyword["accountProperty"] = json!(property);
+            }
+            let schema = json!({"properties": {payment: {"ruBankAccount": keyword}}});
+            let walker = walker(&schema);
+            let validator = with_custom_keywords(jsonschema::options(), Language::En)
+                .build(&schema)
+                .unwrap();
+
+            let valid = json!({payment: {property: "40702810938000000001", "bik": "044525225"}});
+            assert!(validator.is_valid(&valid));
+
+            let invalid = json!({payment: {property: "40702810438000000001", "bik": "044525225"}});
+            let error = validator.iter_errors(&invalid).next().unwrap();
+            let (path, value) = error_target(&error, &walker);
+            assert_eq!(path, format!("/{payment}/{property}"));
+            assert_eq!(value, &json!("40702810438000000001"));
+        }
+    }
+
+    #[test]
+    fn date_limits() {
+        let schema = json!({
+            "properties": {
+                "date": {"format": "date", "formatMinimum": "2000-01-01", "formatMaximum": "2000-12-31"},
+                "moment": {"format": "date-time", "formatMinimum": "2000-01-01T00:00:00+03:00"},
+                "local": {"format": "local-date-time", "formatMaximum": "2000-01-01T00:00:00"}
+            }
+        });
+        let validator = with_custom_keywords(jsonschema::options(), Language::En)
+            .build(&schema)
+            .unwrap();
+
+        assert!(validator.is_valid(&json!({
+            "date": "2000-01-01",
+            "moment": "1999-12-31T21:00:00Z",
+            "local": "1999-12-31T23:59:59.999"
+        })));
+        assert!(!validator.is_valid(&json!({"date": "1999-12-31"})));
+        assert!(!validator.is_valid(&json!({"date": "2001-01-01"})));
+        assert!(!validator.is_valid(&json!({"moment": "1999-12-31T20:59:59Z"})));
+        assert!(!validator.is_valid(&json!({"local": "2000-01-01T00:00:00.1"})));
+
+        let instance = json!({"date": "2001-01-01"});
+        let error = validator.iter_errors(&instance).next().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "\"2001-01-01\" is later than \"2000-12-31\""
+        );
+    }
+
+    #[test]
+    fn not_in_future() {
+        let schema = json!({
+            "properties": {
+                "date": {"format": "date", "notInFuture": true},
+                "moment": {"format": "date-time", "notInFuture": true},
+                "any": {"format": "date", "notInFuture": false}
+            }
+        });
+        let validator = with_custom_keywords(jsonschema::options(), Language::En)
+            .build(&schema)
+            .unwrap();
+
+        assert!(validator.is_valid(&json!({
+            "date": "2000-01-01",
+            "moment": "2000-01-01T00:00:00Z",
+            "any": "9999-12-31"
+        })));
+        assert!(!validator.is_valid(&json!({"date": "9999-12-31"})));
+        assert!(!validator.is_valid(&json!({"moment": "9999-12-31T00:00:00+14:00"})));
+    }
+
+    #[test]
+    fn invalid_keyword_value() {
+        let schema = json!({"ruBankAccount": {"bikProperty": 1}});
+        let options = with_custom_keywords(jsonschema::options(), Language::En);
+        assert!(options.build(&schema).is_err());
+
+        for schema in [
+            json!({"format": "date", "formatMinimum": "01.01.2000"}),
+            json!({"format": "date", "formatMaximum": 2000}),
+            json!({"formatMinimum": 2000}),
+            json!({"format": "date", "notInFuture": "yes"}),
+        ] {
+            let options = with_custom_keywords(jsonschema::options(), Language::En);
+            assert!(options.build(&schema).is_err(), "{schema}");
+        }
+    }
+
+    #[test]
+    fn date_keywords_without_date_format() {
+        // Formats the keywords cannot compare leave them unchecked, as in ajv-formats
+        for schema in [
+            json!({"format": "time", "formatMinimum": "09:00:00"}),
+            json!({"format": "email", "formatMaximum": "z"}),
+            json!({"notInFuture": true}),
+            json!({
+                "$defs": {"d": {"format": "date"}},
+                "$ref": "#/$defs/d",
+                "formatMinimum": "2000-01-01"
+            }),
+        ] {
+            let validator = with_custom_keywords(jsonschema::options(), Language::En)
+                .build(&schema)
+                .unwrap();
+            assert!(validator.is_valid(&json!("08:00:00")), "{schema}");
+            assert!(validator.is_valid(&json!("1999-01-01")), "{schema}");
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use crate::error_details::keyword;
+use crate::schema_walker::SchemaWalker;
+use jsonschema::ValidationError;
+use serde_json::Value;
+
+/// `title` and `description` of the subschema a validation error comes from.
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct Labels<'a> {
+    pub title: Option<&'a str>,
+    pub description: Option<&'a str>,
+}
+
+/// Labels of the subschema holding the failed keyword. For `required` it is the schema of
+/// the object missing the property. A subschema without labels takes them from the schema
+/// referencing it through `$ref`, so labels placed next to `$ref` are found too.
+pub fn labels<'a>(walker: &'a SchemaWalker, error: &ValidationError) -> Labels<'a> {
+    let tokens = evaluation_tokens(error);
+    let mut end = keyword_position(error, &tokens);
+
+    let mut labels = Labels::default();
+
+    loop {
+        if let Some(scope) = walker.at_evaluation_path(&tokens[..end].join("/")) {
+            let label = |name: &str| scope.schema.get(name).and_then(Value::as_str);
+            labels.title = labels.title.or_else(|| label("title"));
+            labels.description = labels.description.or_else(|| label("description"));
+        }
+
+        let complete = labels.title.is_some() && labels.description.is_some();
+        if complete || end == 0 || tokens[end - 1] != "$ref" {
+            return labels;
+        }
+        end -= 1;
+    }
+}
+
+/// Reference tokens of the error's evaluation path, the first one is empty.
+pub fn evaluation_tokens<'e>(error: &'e ValidationError) -> Vec<&'e str> {
+    error.evaluation_path().as_str().split('/').collect()
+}
+
+/// Position of the failed keyword's token, so `tokens[..position]` is its subschema.
+pub fn keyword_position(error: &ValidationError, tokens: &[&str]) -> usize {
+    let keyword = keyword(error.kind());
+
+    // Some keywords report a location below their own token, e.g. `dependentRequired/a`
+    tokens
+        .iter()
+        .rposition(|token| *token == keyword)
+        .unwrap_or(tokens.len().saturating_sub(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{labels, Labels};
+    use crate::schema_walker::testing::{map_errors, walker};
+    use serde_json::{json, Value};
+
+    fn error_labels(schema: &Value, instance: &Value) -> Vec<(Option<String>, Option<String>)> {
+        map_errors(schema, instance, |walker, error| {
+            let Labels { title, description } = labels(walker, error);
+            (title.map(str::to_string), description.map(str::to_string))
+        })
+    }
+
+    #[test]
+    fn failing_subschema() {
+        let schema = json!({
+            "properties": {
+                "kpp": {"title": "КПП", "description": "Код причины постановки на учет", "pattern": "^[0-9]{9}$"},
+                "inn": {"type": "string"}
+            }
+        });
+
+        assert_eq!(
+            error_labels(&schema, &json!({"kpp": "1"})),
+            vec![(
+                Some("КПП".to_string()),
+                Some("Код причины постановки на учет".to_string())
+            )]
+        );
+        assert_eq!(
+            error_labels(&schema, &json!({"inn": 1})),
+            vec![(None, None)]
+        );
+    }
+
+    #[test]
+    fn required_and_references() {
+        let schema = json!({
+            "$defs": {
+                "party": {"description": "Участник", "required": ["inn"]},
+                "date": {"title": "Дата", "format": "date"}
+            },
+            "properties": {
+                "buyer": {"title": "Покупатель", "$ref": "#/$defs/party"},
+                "date": {"title": "Дата договора", "$ref": "#/$defs/date"}
+            }
+        });
+        let options = jsonschema::options().should_validate_formats(true);
+        let validator = options.build(&schema).unwrap();
+        let walker = walker(&schema);
+
+        let instance = json!({"buyer": {}, "date": "2024-02-30"});
+        let found: Vec<Labels> = validator
+            .iter_errors(&instance)
+            .map(|error| labels(&walker, &error))
+            .collect();
+
+        assert_eq!(
+            found,
+            vec![
+                Labels {
+                    title: Some("Покупатель"),
+                    description: Some("Участник")
+                },
+                Labels {
+                    title: Some("Дата"),
+                    description: None
+                }
+            ]
+        );
+    }
+}
@@ -1,9 +1,24 @@
 #![warn(clippy::pedantic)]
 
+mod coercion;
+mod custom_messages;
+mod defaults;
+mod error_details;
 mod errors;
 mod formats;
 mod json_schema_1c;
+mod keywords;
+mod labels;
+mod messages;
+mod onec_path;
+mod output_format;
+mod positions;
+mod prune;
+mod remote_retriever;
 mod retrieve_handler;
+mod schema_files;
+mod schema_walker;
+mod severity;
 mod tools;
 
 use addin1c::{create_component, destroy_component, name, AttachType};
@@ -15,8 +30,8 @@ static PLATFORM_CAPABILITIES: AtomicI32 = AtomicI32::new(-1);
 
 #[no_mangle]
 unsafe extern "C" fn GetClassObject(name: *const u16, component: *mut *mut c_void) -> c_long {
-    match unsafe { *name } as u8 {
-        b'1' => unsafe { create_component(component, json_schema_1c::JsonSchema1C::default()) },
+    match u8::try_from(unsafe { *name }) {
+        Ok(b'1') => unsafe { create_component(component, json_schema_1c::JsonSchema1C::default()) },
         _ => 0,
     }
 }
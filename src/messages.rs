@@ -0,0 +1,290 @@
+use jsonschema::error::{TypeKind, ValidationErrorKind};
+use jsonschema::{JsonType, ValidationError};
+use serde_json::Value;
+use std::str::FromStr;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    En,
+    Ru,
+}
+
+impl Language {
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::Ru => "ru",
+        }
+    }
+}
+
+impl FromStr for Language {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "en" => Ok(Self::En),
+            "ru" => Ok(Self::Ru),
+            _ => Err(()),
+        }
+    }
+}
+
+const FORMAT_TITLES_RU: &[(&str, &str)] = &[
+    ("ru-inn-individual", "ИНН физического лица"),
+    ("ru-inn-legal-entity", "ИНН юридического лица"),
+    ("ru-snils", "СНИЛС"),
+    ("ru-ogrn", "ОГРН"),
+    ("ru-ogrnip", "ОГРНИП"),
+    ("ru-kpp", "КПП"),
+    ("ru-bik", "БИК"),
+    ("kz-iin", "ИИН Казахстана"),
+    ("kz-bin", "БИН Казахстана"),
+    ("by-unp", "УНП Беларуси"),
+    ("uz-inn", "ИНН Узбекистана"),
+    ("kg-inn", "ИНН Кыргызстана"),
+    ("local-date-time", "локальные дата и время"),
+    ("local-date", "локальная дата"),
+    ("local-time", "локальное время"),
+    ("onec-date", "дата 1С"),
+    ("onec-ref", "ссылка 1С"),
+    ("date-time", "дата и время"),
+    ("date", "дата"),
+    ("time", "время"),
+    ("duration", "продолжительность"),
+    ("email", "адрес электронной почты"),
+    ("idn-email", "адрес электронной почты"),
+    ("hostname", "имя хоста"),
+    ("idn-hostname", "имя хоста"),
+    ("ipv4", "IPv4-адрес"),
+    ("ipv6", "IPv6-адрес"),
+    ("uri", "URI"),
+    ("uri-reference", "ссылка URI"),
+    ("iri", "IRI"),
+    ("iri-reference", "ссылка IRI"),
+    ("uri-template", "шаблон URI"),
+    ("uuid", "UUID"),
+    ("json-pointer", "JSON Pointer"),
+    ("relative-json-pointer", "относительный JSON Pointer"),
+    ("regex", "регулярное выражение"),
+];
+
+pub fn message(error: &ValidationError, language: Language) -> String {
+    match language {
+        Language::En => error.to_string(),
+        Language::Ru => message_ru(error.kind(), error.instance()),
+    }
+}
+
+#[allow(clippy::too_many_lines)] // Message catalogue, one arm per keyword
+fn message_ru(kind: &ValidationErrorKind, instance: &Value) -> String {
+    match kind {
+        ValidationErrorKind::AdditionalItems { limit } => {
+            format!("Массив {instance} содержит лишние элементы (допустимо не более {limit})")
+        }
+        ValidationErrorKind::AdditionalProperties { unexpected } => {
+            format!(
+                "Дополнительные свойства не допускаются ({})",
+                quoted_list(unexpected)
+            )
+        }
+        ValidationErrorKind::AnyOf { .. } => {
+            format!("{instance} не соответствует ни одной из схем в 'anyOf'")
+        }
+        ValidationErrorKind::BacktrackLimitExceeded { error } => {
+            format!("Превышен лимит вычислений регулярного выражения: {error}")
+        }
+        ValidationErrorKind::Constant { expected_value } => {
+            format!("{instance} не равно ожидаемому значению {expected_value}")
+        }
+        ValidationErrorKind::Contains => {
+            format!("{instance} не содержит ни одного подходящего элемента")
+        }
+        ValidationErrorKind::ContentEncoding { content_encoding } => {
+            format!("{instance} не соответствует кодировке \"{content_encoding}\"")
+        }
+        ValidationErrorKind::ContentMediaType { content_media_type } => {
+            format!("{instance} не соответствует типу содержимого \"{content_media_type}\"")
+        }
+        ValidationErrorKind::Custom { message, .. } => message.clone(),
+        ValidationErrorKind::Enum { options } => {
+            let options = options
+                .as_array()
+                .map(|o| {
+                    o.iter()
+                        .map(Value::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .unwrap_or_default();
+            format!("{instance} не является одним из допустимых значений: {options}")
+        }
+        ValidationErrorKind::ExclusiveMaximum { limit } => {
+            format!("{instance} должно быть меньше {limit}")
+        }
+        ValidationErrorKind::ExclusiveMinimum { limit } => {
+            format!("{instance} должно быть больше {limit}")
+        }
+        ValidationErrorKind::FalseSchema => format!("Значение {instance} запрещено схемой"),
+        ValidationErrorKind::Format { format } => {
+            format!(
+                "{instance} не соответствует формату «{}»",
+                format_title(format)
+            )
+        }
+        ValidationErrorKind::FromUtf8 { error } => {
+            format!("{instance} содержит некорректную последовательность UTF-8: {error}")
+        }
+        ValidationErrorKind::MaxItems { limit } => {
+            format!("Массив {instance} должен содержать не более {limit} эл.")
+        }
+        ValidationErrorKind::Maximum { limit } => {
+            format!("{instance} больше максимально допустимого значения {limit}")
+        }
+        ValidationErrorKind::MaxLength { limit } => {
+            format!("Длина строки {instance} должна быть не более {limit} симв.")
+        }
+        ValidationErrorKind::MaxProperties { limit } => {
+            format!("Объект {instance} должен содержать не более {limit} свойств")
+        }
+        ValidationErrorKind::MinItems { limit } => {
+            format!("Массив {instance} должен содержать не менее {limit} эл.")
+        }
+        ValidationErrorKind::Minimum { limit } => {
+            format!("{instance} меньше минимально допустимого значения {limit}")
+        }
+        ValidationErrorKind::MinLength { limit } => {
+            format!("Длина строки {instance} должна быть не менее {limit} симв.")
+        }
+        ValidationErrorKind::MinProperties { limit } => {
+            format!("Объект {instance} должен содержать не менее {limit} свойств")
+        }
+        ValidationErrorKind::MultipleOf { multiple_of } => {
+            format!("{instance} не кратно {multiple_of}")
+        }
+        ValidationErrorKind::Not { schema } => {
+            format!("{instance} не должно соответствовать схеме {schema}")
+        }
+        ValidationErrorKind::OneOfMultipleValid { .. } => {
+            format!("{instance} соответствует более чем одной схеме в 'oneOf'")
+        }
+        ValidationErrorKind::OneOfNotValid { .. } => {
+            format!("{instance} не соответствует ни одной из схем в 'oneOf'")
+        }
+        ValidationErrorKind::Pattern { pattern } => {
+            format!("{instance} не соответствует шаблону \"{pattern}\"")
+        }
+        ValidationErrorKind::PropertyNames { error } => {
+            format!(
+                "Недопустимое имя свойства: {}",
+                message_ru(error.kind(), error.instance())
+            )
+        }
+        ValidationErrorKind::Required { property } => {
+            format!("Отсутствует обязательное свойство {property}")
+        }
+        ValidationErrorKind::Type { kind } => {
+            let expected = match kind {
+                TypeKind::Single(t) => type_name(*t).to_string(),
+                TypeKind::Multiple(set) => {
+                    set.iter().map(type_name).collect::<Vec<_>>().join(" или ")
+                }
+            };
+            format!("{instance} имеет неверный тип, ожидается: {expected}")
+        }
+        ValidationErrorKind::UnevaluatedItems { unexpected } => {
+            format!(
+                "Непроверенные элементы массива не допускаются ({})",
+                unexpected.join(", ")
+            )
+        }
+        ValidationErrorKind::UnevaluatedProperties { unexpected } => {
+            format!(
+                "Непроверенные свойства не допускаются ({})",
+                quoted_list(unexpected)
+            )
+        }
+        ValidationErrorKind::UniqueItems => {
+            format!("Массив {instance} содержит повторяющиеся элементы")
+        }
+        ValidationErrorKind::Referencing(error) => {
+            format!("Ошибка разрешения ссылки: {error}")
+        }
+    }
+}
+
+fn format_title(format: &str) -> &str {
+    FORMAT_TITLES_RU
+        .iter()
+        .find(|(name, _)| *name == format)
+        .map_or(format, |(_, title)| title)
+}
+
+fn type_name(json_type: JsonType) -> &'static str {
+    match json_type {
+        JsonType::Array => "массив",
+        JsonType::Boolean => "булево",
+        JsonType::Integer => "целое число",
+        JsonType::Null => "null",
+        JsonType::Number => "число",
+        JsonType::Object => "объект",
+        JsonType::String => "строка",
+    }
+}
+
+fn quoted_list(items: &[String]) -> String {
+    items
+        .iter()
+        .map(|item| format!("'{item}'"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{message, Language};
+    use serde_json::json;
+
+    fn first_error(schema: &serde_json::Value, instance: &serde_json::Value) -> String {
+        let validator = jsonschema::options()
+            .should_validate_formats(true)
+            .with_format("ru-inn-legal-entity", |_: &str| false)
+            .build(schema)
+            .unwrap();
+        let error = validator.iter_errors(instance).next().unwrap();
+        message(&error, Language::Ru)
+    }
+
+    #[test]
+    fn type_message_ru() {
+        assert_eq!(
+            first_error(&json!({"type": ["string", "null"]}), &json!(1)),
+            "1 имеет неверный тип, ожидается: null или строка"
+        );
+    }
+
+    #[test]
+    fn required_message_ru() {
+        assert_eq!(
+            first_error(&json!({"required": ["kpp"]}), &json!({})),
+            "Отсутствует обязательное свойство \"kpp\""
+        );
+    }
+
+    #[test]
+    fn custom_format_message_ru() {
+        assert_eq!(
+            first_error(&json!({"format": "ru-inn-legal-entity"}), &json!("1")),
+            "\"1\" не соответствует формату «ИНН юридического лица»"
+        );
+    }
+
+    #[test]
+    fn english_is_library_default() {
+        let validator = jsonschema::validator_for(&json!({"minLength": 2})).unwrap();
+        let instance = json!("a");
+        let error = validator.iter_errors(&instance).next().unwrap();
+        assert_eq!(message(&error, Language::En), error.to_string());
+    }
+}
@@ -0,0 +1,80 @@
+use serde_json::Value;
+
+/// Renders a JSON Pointer into `instance` in 1C notation: `Товары[4].Количество`.
+/// Array indexes start from `first_index`; keys that are not 1C identifiers are written
+/// as `["key"]`.
+pub fn onec_path(instance: &Value, pointer: &str, first_index: usize) -> String {
+    let mut path = String::new();
+    let mut current = Some(instance);
+
+    for token in pointer.split('/').skip(1) {
+        let token = token.replace("~1", "/").replace("~0", "~");
+
+        let index = match current {
+            Some(Value::Array(_)) => token.parse::<usize>().ok(),
+            _ => None,
+        };
+
+        if let Some(index) = index {
+            path.push('[');
+            path.push_str(&(index + first_index).to_string());
+            path.push(']');
+            current = current.and_then(|value| value.get(index));
+            continue;
+        }
+
+        if is_identifier(&token) {
+            if !path.is_empty() {
+                path.push('.');
+            }
+            path.push_str(&token);
+        } else {
+            path.push_str("[\"");
+            path.push_str(&token.replace('"', "\"\""));
+            path.push_str("\"]");
+        }
+        current = current.and_then(|value| value.get(&token));
+    }
+
+    path
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_alphabetic() || first == '_')
+        && chars.all(|ch| ch.is_alphanumeric() || ch == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::onec_path;
+    use serde_json::json;
+
+    #[test]
+    fn properties_and_items() {
+        let instance = json!({"Товары": [{}, {}, {}, {"Количество": 0}]});
+
+        assert_eq!(
+            onec_path(&instance, "/Товары/3/Количество", 1),
+            "Товары[4].Количество"
+        );
+        assert_eq!(
+            onec_path(&instance, "/Товары/3/Количество", 0),
+            "Товары[3].Количество"
+        );
+        assert_eq!(onec_path(&instance, "", 1), "");
+    }
+
+    #[test]
+    fn keys_that_are_not_identifiers() {
+        let instance = json!({"3": {"a/b": {"Вид \"x\"": 1}}, "list": [[1, 2]]});
+
+        assert_eq!(
+            onec_path(&instance, "/3/a~1b/Вид \"x\"", 1),
+            "[\"3\"][\"a/b\"][\"Вид \"\"x\"\"\"]"
+        );
+        assert_eq!(onec_path(&instance, "/list/0/1", 1), "list[1][2]");
+    }
+}
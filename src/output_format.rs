@@ -0,0 +1,263 @@
+use crate::onec_path::onec_path;
+use jsonschema::Evaluation;
+use serde_json::{Map, Value};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Flag,
+    Basic,
+    Detailed,
+    Verbose,
+}
+
+impl FromStr for OutputFormat {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "flag" => Ok(Self::Flag),
+            "basic" => Ok(Self::Basic),
+            "detailed" => Ok(Self::Detailed),
+            "verbose" => Ok(Self::Verbose),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Renders the evaluation as a JSON Schema 2019-09 / 2020-12 output structure.
+pub fn render(
+    evaluation: &Evaluation,
+    instance: &Value,
+    format: OutputFormat,
+) -> Result<Value, serde_json::Error> {
+    if format == OutputFormat::Flag {
+        return serde_json::to_value(evaluation.flag());
+    }
+
+    let root = serde_json::to_value(evaluation.hierarchical())?;
+    let valid = is_valid(&root);
+
+    Ok(match format {
+        OutputFormat::Flag => unreachable!("Flag format is handled above"),
+        OutputFormat::Basic => {
+            let mut units = Vec::new();
+            collect_basic(&root, instance, valid, &mut units);
+
+            let mut output = Map::new();
+            output.insert("valid".to_string(), Value::Bool(valid));
+            if !units.is_empty() {
+                output.insert(children_key(valid).to_string(), Value::Array(units));
+            }
+            Value::Object(output)
+        }
+        OutputFormat::Detailed => match detailed(&root, instance, valid) {
+            Some(mut unit) => {
+                // The root unit always reports the overall result
+                unit.insert("valid".to_string(), Value::Bool(valid));
+                Value::Object(unit)
+            }
+            None => Value::Object(unit(&root, instance)),
+        },
+        OutputFormat::Verbose => Value::Object(verbose(&root, instance)),
+    })
+}
+
+fn is_valid(node: &Value) -> bool {
+    node.get("valid").and_then(Value::as_bool).unwrap_or(false)
+}
+
+fn children_key(valid: bool) -> &'static str {
+    if valid {
+        "annotations"
+    } else {
+        "errors"
+    }
+}
+
+fn details(node: &Value) -> &[Value] {
+    node.get("details")
+        .and_then(Value::as_array)
+        .map_or(&[], Vec::as_slice)
+}
+
+fn error_message(node: &Value) -> Option<String> {
+    let errors = node.get("errors")?.as_object()?;
+    let messages: Vec<&str> = errors.values().filter_map(Value::as_str).collect();
+    (!messages.is_empty()).then(|| messages.join("; "))
+}
+
+fn has_annotations(node: &Value) -> bool {
+    node.get("annotations").is_some() || details(node).iter().any(has_annotations)
+}
+
+/// Output unit with location fields renamed to the names used by the specification.
+fn unit(node: &Value, instance: &Value) -> Map<String, Value> {
+    let mut unit = Map::new();
+    unit.insert("valid".to_string(), Value::Bool(is_valid(node)));
+
+    let keyword_location = node.get("evaluationPath").cloned().unwrap_or_default();
+    unit.insert("keywordLocation".to_string(), keyword_location);
+
+    // Locations inside the root resource are relative; absolute ones contain a fragment
+    if let Some(location) = node.get("schemaLocation").and_then(Value::as_str) {
+        if location.contains('#') {
+            unit.insert(
+                "absoluteKeywordLocation".to_string(),
+                Value::String(location.to_string()),
+            );
+        }
+    }
+
+    let instance_location = node.get("instanceLocation").cloned().unwrap_or_default();
+    if let Some(pointer) = instance_location.as_str() {
+        unit.insert(
+            "instanceLocation1C".to_string(),
+            Value::String(onec_path(instance, pointer, 1)),
+        );
+    }
+    unit.insert("instanceLocation".to_string(), instance_location);
+
+    if let Some(message) = error_message(node) {
+        unit.insert("error".to_string(), Value::String(message));
+    }
+
+    if let Some(annotation) = node.get("annotations") {
+        unit.insert("annotation".to_string(), annotation.clone());
+    }
+
+    unit
+}
+
+fn collect_basic(node: &Value, instance: &Value, valid: bool, units: &mut Vec<Value>) {
+    let keep = if valid {
+        node.get("annotations").is_some()
+    } else {
+        !is_valid(node) && node.get("errors").is_some()
+    };
+
+    if keep {
+        units.push(Value::Object(unit(node, instance)));
+    }
+
+    for child in details(node) {
+        collect_basic(child, instance, valid, units);
+    }
+}
+
+fn detailed(node: &Value, instance: &Value, valid: bool) -> Option<Map<String, Value>> {
+    let relevant = if valid {
+        has_annotations(node)
+    } else {
+        !is_valid(node)
+    };
+
+    if !relevant {
+        return None;
+    }
+
+    let mut unit = unit(node, instance);
+    let mut children: Vec<Map<String, Value>> = details(node)
+        .iter()
+        .filter_map(|child| detailed(child, instance, valid))
+        .collect();
+
+    let has_own_result = unit.contains_key("error") || unit.contains_key("annotation");
+    if !has_own_result && children.len() == 1 {
+        return children.pop();
+    }
+
+    if !children.is_empty() {
+        unit.insert(
+            children_key(valid).to_string(),
+            Value::Array(children.into_iter().map(Value::Object).collect()),
+        );
+    }
+
+    Some(unit)
+}
+
+fn verbose(node: &Value, instance: &Value) -> Map<String, Value> {
+    let mut unit = unit(node, instance);
+    let children: Vec<Value> = details(node)
+        .iter()
+        .map(|child| Value::Object(verbose(child, instance)))
+        .collect();
+
+    if !children.is_empty() {
+        unit.insert(
+            children_key(is_valid(node)).to_string(),
+            Value::Array(children),
+        );
+    }
+
+    unit
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render, OutputFormat};
+    use serde_json::json;
+
+    fn evaluate(instance: &serde_json::Value, format: OutputFormat) -> serde_json::Value {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "name": {"type": "string"},
+                "age": {"type": "integer", "minimum": 0}
+            }
+        });
+        let validator = jsonschema::validator_for(&schema).unwrap();
+        render(&validator.evaluate(instance), instance, format).unwrap()
+    }
+
+    #[test]
+    fn flag_output() {
+        assert_eq!(
+            evaluate(&json!({"age": -1}), OutputFormat::Flag),
+            json!({"valid": false})
+        );
+    }
+
+    #[test]
+    fn basic_output() {
+        assert_eq!(
+            evaluate(&json!({"name": 1, "age": 5}), OutputFormat::Basic),
+            json!({
+                "valid": false,
+                "errors": [{
+                    "valid": false,
+                    "keywordLocation": "/properties/name/type",
+                    "instanceLocation": "/name",
+                    "instanceLocation1C": "name",
+                    "error": "1 is not of type \"string\""
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn detailed_output_is_condensed() {
+        let output = evaluate(&json!({"name": 1, "age": -1}), OutputFormat::Detailed);
+        let errors = output["errors"].as_array().unwrap();
+
+        assert_eq!(output["valid"], json!(false));
+        assert_eq!(output["keywordLocation"], json!("/properties"));
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|e| e.get("error").is_some()));
+    }
+
+    #[test]
+    fn verbose_output_contains_valid_units() {
+        let output = evaluate(&json!({"name": "Ann"}), OutputFormat::Verbose);
+
+        assert_eq!(output["valid"], json!(true));
+        assert_eq!(output["annotations"][0]["keywordLocation"], json!("/type"));
+    }
+
+    #[test]
+    fn unknown_format() {
+        assert!("compact".parse::<OutputFormat>().is_err());
+        assert_eq!("Basic".parse(), Ok(OutputFormat::Basic));
+    }
+}
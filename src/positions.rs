@@ -0,0 +1,279 @@
+use crate::schema_walker::pointer_push;
+use std::collections::{HashMap, HashSet};
+
+/// Place of a value in the source JSON text. Line and column start from 1, the column
+/// counts characters; the offset counts bytes of the UTF-8 text from 0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+    pub offset: usize,
+}
+
+/// Positions of the values at the requested JSON Pointers.
+#[derive(Default)]
+pub struct Positions(HashMap<String, Position>);
+
+impl Positions {
+    /// Finds `pointers` in `source`, which must be valid JSON, in a single pass.
+    /// Subtrees not leading to any of them are skipped without tracking.
+    pub fn find<'p>(source: &[u8], pointers: impl IntoIterator<Item = &'p str>) -> Self {
+        let mut wanted = HashSet::new();
+
+        for pointer in pointers {
+            // Ancestors are kept to descend to the pointer and to stand in for missing values
+            let mut end = pointer.len();
+            while wanted.insert(pointer[..end].to_string()) && end > 0 {
+                end = pointer[..end].rfind('/').unwrap_or(0);
+            }
+        }
+
+        let mut scanner = Scanner {
+            source,
+            pos: 0,
+            line: 1,
+            line_start: 0,
+            wanted: &wanted,
+            found: HashMap::new(),
+        };
+        scanner.value(&mut String::new());
+
+        Self(scanner.found)
+    }
+
+    /// Position of the value at `pointer`, or of its nearest ancestor present in the text,
+    /// e.g. the object missing a required property filled in with a default.
+    pub fn get(&self, pointer: &str) -> Option<Position> {
+        let mut pointer = pointer;
+        loop {
+            if let Some(position) = self.0.get(pointer) {
+                return Some(*position);
+            }
+            pointer = &pointer[..pointer.rfind('/')?];
+        }
+    }
+}
+
+struct Scanner<'a> {
+    source: &'a [u8],
+    pos: usize,
+    line: usize,
+    line_start: usize,
+    wanted: &'a HashSet<String>,
+    found: HashMap<String, Position>,
+}
+
+impl Scanner<'_> {
+    fn value(&mut self, pointer: &mut String) -> Option<()> {
+        self.skip_whitespace();
+
+        if !self.wanted.contains(pointer.as_str()) {
+            return self.skip_value();
+        }
+        self.found.insert(pointer.clone(), self.position());
+
+        match self.source.get(self.pos)? {
+            b'{' => self.object(pointer),
+            b'[' => self.array(pointer),
+            _ => self.skip_value(),
+        }
+    }
+
+    fn object(&mut self, pointer: &mut String) -> Option<()> {
+        self.pos += 1;
+
+        loop {
+            self.skip_whitespace();
+            if self.source.get(self.pos)? == &b'}' {
+                self.pos += 1;
+                return Some(());
+            }
+
+            let key = self.string()?;
+            self.skip_whitespace();
+            self.expect(b':')?;
+
+            let len = pointer.len();
+            *pointer = pointer_push(pointer, &key);
+            self.value(pointer)?;
+            pointer.truncate(len);
+
+            self.skip_whitespace();
+            if self.source.get(self.pos)? == &b',' {
+                self.pos += 1;
+            }
+        }
+    }
+
+    fn array(&mut self, pointer: &mut String) -> Option<()> {
+        self.pos += 1;
+
+        for index in 0.. {
+            self.skip_whitespace();
+            if self.source.get(self.pos)? == &b']' {
+                self.pos += 1;
+                break;
+            }
+
+            let len = pointer.len();
+            *pointer = pointer_push(pointer, &index.to_string());
+            self.value(pointer)?;
+            pointer.truncate(len);
+
+            self.skip_whitespace();
+            if self.source.get(self.pos)? == &b',' {
+                self.pos += 1;
+            }
+        }
+
+        Some(())
+    }
+
+    /// Skips a value of any kind; nested brackets are only counted.
+    fn skip_value(&mut self) -> Option<()> {
+        let mut depth = 0usize;
+
+        loop {
+            match self.source.get(self.pos)? {
+                b'"' => self.skip_string()?,
+                b'{' | b'[' => {
+                    depth += 1;
+                    self.pos += 1;
+                }
+                b'}' | b']' => {
+                    depth = depth.checked_sub(1)?;
+                    self.pos += 1;
+                }
+                b'\n' => {
+                    self.pos += 1;
+                    self.line += 1;
+                    self.line_start = self.pos;
+                }
+                _ => self.pos += 1,
+            }
+
+            if depth == 0 && self.is_value_end() {
+                return Some(());
+            }
+        }
+    }
+
+    /// Whether a scalar or a closed container ends at the current position.
+    fn is_value_end(&self) -> bool {
+        self.source
+            .get(self.pos)
+            .is_none_or(|b| matches!(b, b',' | b'}' | b']' | b' ' | b'\t' | b'\r' | b'\n'))
+    }
+
+    /// Reads a string and returns its unescaped contents.
+    fn string(&mut self) -> Option<String> {
+        let start = self.pos;
+        self.skip_string()?;
+        let raw = &self.source[start..self.pos];
+
+        if raw.contains(&b'\\') {
+            serde_json::from_slice(raw).ok()
+        } else {
+            String::from_utf8(raw[1..raw.len() - 1].to_vec()).ok()
+        }
+    }
+
+    fn skip_string(&mut self) -> Option<()> {
+        self.expect(b'"')?;
+
+        loop {
+            match self.source.get(self.pos)? {
+                b'"' => {
+                    self.pos += 1;
+                    return Some(());
+                }
+                b'\\' => self.pos += 2,
+                _ => self.pos += 1,
+            }
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b) = self.source.get(self.pos) {
+            match b {
+                b'\n' => {
+                    self.line += 1;
+                    self.line_start = self.pos + 1;
+                }
+                b' ' | b'\t' | b'\r' => {}
+                _ => return,
+            }
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Option<()> {
+        (self.source.get(self.pos)? == &byte).then(|| self.pos += 1)
+    }
+
+    fn position(&self) -> Position {
+        let line = &self.source[self.line_start..self.pos];
+
+        Position {
+            line: self.line,
+            column: String::from_utf8_lossy(line).chars().count() + 1,
+            offset: self.pos,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Position, Positions};
+
+    #[test]
+    fn values_and_missing_ones() {
+        let source = "{\n  \"Товары\": [\n    {\"Цена\": 1},\n    {\"a/b\": \"x\\\"}\", \"Цена\": -5}\n  ],\n  \"note\": null\n}";
+        let positions = Positions::find(
+            source.as_bytes(),
+            [
+                "/Товары/1/Цена",
+                "/Товары/1/a~1b",
+                "/note",
+                "/Товары/0/Количество",
+            ],
+        );
+
+        let at = |pointer: &str| {
+            let position = positions.get(pointer).unwrap();
+            (position.line, position.column, position.offset)
+        };
+
+        assert_eq!(at("/Товары/1/Цена"), (4, 29, source.find("-5").unwrap()));
+        assert_eq!(at("/Товары/1/a~1b"), (4, 13, source.find("\"x").unwrap()));
+        assert_eq!(at("/note"), (6, 11, source.find("null").unwrap()));
+        assert_eq!(
+            at("/Товары/0/Количество"),
+            (3, 5, source.find("{\"Цена").unwrap())
+        );
+        assert_eq!(
+            positions.get(""),
+            Some(Position {
+                line: 1,
+                column: 1,
+                offset: 0
+            })
+        );
+    }
+
+    #[test]
+    fn skipped_subtrees() {
+        let source = r#"[{"a": [1, {"b": "]"}]}, "x", 2.5e3, {"c": true}]"#;
+        let positions = Positions::find(source.as_bytes(), ["/3/c", "/2"]);
+
+        assert_eq!(
+            positions.get("/2").unwrap().offset,
+            source.find("2.5").unwrap()
+        );
+        assert_eq!(
+            positions.get("/3/c").unwrap().offset,
+            source.find("true").unwrap()
+        );
+        assert!(positions.get("/0").is_some_and(|p| p.offset == 0));
+    }
+}
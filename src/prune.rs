@@ -0,0 +1,115 @@
+use crate::schema_walker::pointer_push;
+use jsonschema::error::ValidationErrorKind;
+use jsonschema::Validator;
+use serde_json::Value;
+
+/// Removes properties rejected by `additionalProperties: false` or
+/// `unevaluatedProperties: false` and returns JSON Pointers of the removed ones.
+pub fn prune_additional(validator: &Validator, instance: &mut Value) -> Vec<String> {
+    let mut removed = Vec::new();
+
+    // Removing a property may change which branches match, so repeat until nothing is rejected
+    loop {
+        let rejected: Vec<(String, Vec<String>)> = validator
+            .iter_errors(instance)
+            .filter_map(|error| match error.kind() {
+                ValidationErrorKind::AdditionalProperties { unexpected }
+                | ValidationErrorKind::UnevaluatedProperties { unexpected } => Some((
+                    error.instance_path().as_str().to_string(),
+                    unexpected.clone(),
+                )),
+                _ => None,
+            })
+            .collect();
+
+        let count = removed.len();
+
+        for (path, names) in rejected {
+            let Some(object) = instance.pointer_mut(&path).and_then(Value::as_object_mut) else {
+                continue;
+            };
+
+            for name in names {
+                if object.remove(&name).is_some() {
+                    removed.push(pointer_push(&path, &name));
+                }
+            }
+        }
+
+        if removed.len() == count {
+            return removed;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::prune_additional;
+    use serde_json::{json, Value};
+
+    fn pruned(schema: &Value, mut instance: Value) -> (Value, Vec<String>) {
+        let validator = jsonschema::validator_for(schema).unwrap();
+        let removed = prune_additional(&validator, &mut instance);
+        (instance, removed)
+    }
+
+    #[test]
+    fn additional_properties() {
+        let schema = json!({
+            "properties": {
+                "inn": {"type": "string"},
+                "lines": {
+                    "items": {
+                        "properties": {"qty": {}},
+                        "patternProperties": {"^x-": {}},
+                        "additionalProperties": false
+                    }
+                }
+            },
+            "additionalProperties": false
+        });
+
+        assert_eq!(
+            pruned(
+                &schema,
+                json!({"inn": "1", "note": "?", "lines": [{"qty": 1, "x-id": 5, "a/b": 0}]})
+            ),
+            (
+                json!({"inn": "1", "lines": [{"qty": 1, "x-id": 5}]}),
+                vec!["/lines/0/a~1b".to_string(), "/note".to_string()]
+            )
+        );
+    }
+
+    #[test]
+    fn unevaluated_properties() {
+        let schema = json!({
+            "allOf": [{"properties": {"inn": {}}}],
+            "if": {"properties": {"kind": {"const": "legal"}}},
+            "then": {"properties": {"kind": {}, "kpp": {}}},
+            "else": {"properties": {"kind": {}}},
+            "unevaluatedProperties": false
+        });
+
+        assert_eq!(
+            pruned(
+                &schema,
+                json!({"kind": "legal", "inn": "1", "kpp": "2", "x": 0})
+            ),
+            (
+                json!({"kind": "legal", "inn": "1", "kpp": "2"}),
+                vec!["/x".to_string()]
+            )
+        );
+        assert_eq!(
+            pruned(&schema, json!({"kind": "person", "kpp": "2"})).1,
+            vec!["/kpp".to_string()]
+        );
+    }
+
+    #[test]
+    fn nothing_to_remove() {
+        let schema = json!({"properties": {"inn": {}}});
+        assert!(pruned(&schema, json!({"inn": "1", "x": 0})).1.is_empty());
+    }
+}
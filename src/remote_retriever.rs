@@ -0,0 +1,307 @@
+use crate::errors::JsonSchema1CError;
+use jsonschema::Uri;
+use serde_json::Value;
+use std::error::Error;
+use std::fs;
+use std::io::Read;
+use std::path::PathBuf;
+use std::time::Duration;
+
+type RetrieveResult = Result<Value, Box<dyn Error + Send + Sync>>;
+
+/// Redirects followed per request, as in the default reqwest policy.
+const MAX_REDIRECTS: usize = 10;
+
+#[derive(Clone)]
+pub struct RemoteOptions {
+    pub allowed_hosts: Vec<String>,
+    pub timeout: Duration,
+    pub max_size: u64,
+    pub cache_directory: Option<PathBuf>,
+}
+
+impl Default for RemoteOptions {
+    fn default() -> Self {
+        Self {
+            allowed_hosts: Vec::new(),
+            timeout: Duration::from_secs(30),
+            max_size: 10 * 1024 * 1024,
+            cache_directory: None,
+        }
+    }
+}
+
+impl RemoteOptions {
+    /// Remote retrieval is opt-in: nothing is downloaded until a host is allowed.
+    pub fn is_enabled(&self) -> bool {
+        !self.allowed_hosts.is_empty()
+    }
+
+    fn is_host_allowed(&self, host: &str) -> bool {
+        let host = host.to_lowercase();
+        self.allowed_hosts.iter().any(|allowed| {
+            let allowed = allowed.to_lowercase();
+            match allowed.strip_prefix("*.") {
+                Some(domain) => host.ends_with(&format!(".{domain}")),
+                None => host == allowed,
+            }
+        })
+    }
+}
+
+pub struct RemoteRetriever {
+    options: RemoteOptions,
+    client: reqwest::blocking::Client,
+}
+
+impl RemoteRetriever {
+    pub fn new(options: RemoteOptions) -> Result<Self, JsonSchema1CError> {
+        // Install ring as the default TLS crypto provider (only needed once)
+        let _ = rustls::crypto::ring::default_provider().install_default();
+
+        // Zero timeout means no limit
+        let timeout = (!options.timeout.is_zero()).then_some(options.timeout);
+
+        // Every hop must stay within the allow-list, not only the requested URI
+        let redirect_options = options.clone();
+        let redirect = reqwest::redirect::Policy::custom(move |attempt| {
+            let host = attempt.url().host_str().unwrap_or_default();
+            if !redirect_options.is_host_allowed(host) {
+                let error = format!("Redirect to host '{host}' is not allowed");
+                attempt.error(error)
+            } else if attempt.previous().len() > MAX_REDIRECTS {
+                attempt.error("Too many redirects")
+            } else {
+                attempt.follow()
+            }
+        });
+
+        let client = reqwest::blocking::Client::builder()
+            .timeout(timeout)
+            .redirect(redirect)
+            .build()
+            .map_err(|e| JsonSchema1CError::HttpClient(e.to_string()))?;
+
+        Ok(Self { options, client })
+    }
+
+    pub fn retrieve(&self, uri: &Uri<String>) -> RetrieveResult {
+        let host = uri.authority().map(|a| a.host()).unwrap_or_default();
+        if !self.options.is_host_allowed(host) {
+            return Err(format!("Host '{host}' of schema {uri} is not allowed").into());
+        }
+
+        if let Some(value) = self.read_cache(uri) {
+            return Ok(value);
+        }
+
+        let body = self.download(uri)?;
+        let value: Value = serde_json::from_slice(&body)?;
+        self.write_cache(uri, &body);
+        Ok(value)
+    }
+
+    fn download(&self, uri: &Uri<String>) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+        let response = self.client.get(uri.as_str()).send()?.error_for_status()?;
+
+        let max_size = self.options.max_size;
+        let too_large = || format!("Schema {uri} exceeds the size limit of {max_size} bytes");
+
+        if response.content_length().is_some_and(|len| len > max_size) {
+            return Err(too_large().into());
+        }
+
+        let mut body = Vec::new();
+        response.take(max_size + 1).read_to_end(&mut body)?;
+        if body.len() as u64 > max_size {
+            return Err(too_large().into());
+        }
+
+        Ok(body)
+    }
+
+    fn cache_path(&self, uri: &Uri<String>) -> Option<PathBuf> {
+        let dir = self.options.cache_directory.as_ref()?;
+
+        let readable: String = uri
+            .as_str()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .take(100)
+            .collect();
+
+        Some(dir.join(format!("{readable}_{:016x}.json", fnv1a(uri.as_str()))))
+    }
+
+    fn read_cache(&self, uri: &Uri<String>) -> Option<Value> {
+        let content = fs::read(self.cache_path(uri)?).ok()?;
+        serde_json::from_slice(&content).ok()
+    }
+
+    /// A failed cache write only costs a repeated download, so errors are ignored.
+    fn write_cache(&self, uri: &Uri<String>, body: &[u8]) {
+        if let Some(path) = self.cache_path(uri) {
+            if let Some(dir) = path.parent() {
+                let _ = fs::create_dir_all(dir);
+            }
+            let _ = fs::write(path, body);
+        }
+    }
+}
+
+/// 64-bit FNV-1a. Cache file names must survive toolchain upgrades, which the standard
+/// library hasher does not guarantee.
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fnv1a, RemoteOptions, RemoteRetriever};
+    use serde_json::json;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Serves `body` to `requests` connections and returns the server address.
+    fn serve(body: &'static str, requests: usize) -> String {
+        respond(
+            format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            ),
+            requests,
+        )
+    }
+
+    /// Redirects a single request to `location` and returns the server address.
+    fn redirect(location: &str) -> String {
+        respond(
+            format!(
+                "HTTP/1.1 302 Found\r\nLocation: {location}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+            ),
+            1,
+        )
+    }
+
+    fn respond(response: String, requests: usize) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+
+        thread::spawn(move || {
+            for stream in listener.incoming().take(requests) {
+                let mut stream = stream.unwrap();
+                let mut buf = [0; 4096];
+                let _ = stream.read(&mut buf).unwrap();
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        addr
+    }
+
+    fn uri(addr: &str) -> jsonschema::Uri<String> {
+        jsonschema::Uri::parse(format!("http://{addr}/schemas/order.json")).unwrap()
+    }
+
+    fn options(hosts: &[&str]) -> RemoteOptions {
+        RemoteOptions {
+            allowed_hosts: hosts.iter().map(ToString::to_string).collect(),
+            ..RemoteOptions::default()
+        }
+    }
+
+    #[test]
+    fn allowed_host() {
+        let addr = serve(r#"{"type": "string"}"#, 1);
+        let retriever = RemoteRetriever::new(options(&["127.0.0.1"])).unwrap();
+
+        assert_eq!(
+            retriever.retrieve(&uri(&addr)).unwrap(),
+            json!({"type": "string"})
+        );
+    }
+
+    #[test]
+    fn host_not_in_allow_list() {
+        let retriever = RemoteRetriever::new(options(&["*.example.com"])).unwrap();
+        assert!(retriever.retrieve(&uri("127.0.0.1:1")).is_err());
+    }
+
+    #[test]
+    fn redirects() {
+        let options = || options(&["127.0.0.1"]);
+
+        let target = serve(r#"{"type": "string"}"#, 1);
+        let allowed = redirect(&format!("http://{target}/schemas/moved.json"));
+        let retriever = RemoteRetriever::new(options()).unwrap();
+        assert_eq!(
+            retriever.retrieve(&uri(&allowed)).unwrap(),
+            json!({"type": "string"})
+        );
+
+        // Same server under a name missing from the allow-list
+        let port = serve(r#"{"type": "string"}"#, 1)
+            .rsplit_once(':')
+            .unwrap()
+            .1
+            .to_string();
+        let denied = redirect(&format!("http://localhost:{port}/schemas/moved.json"));
+        let retriever = RemoteRetriever::new(options()).unwrap();
+        assert!(retriever.retrieve(&uri(&denied)).is_err());
+    }
+
+    #[test]
+    fn size_limit() {
+        let addr = serve(r#"{"type": "string"}"#, 1);
+        let retriever = RemoteRetriever::new(RemoteOptions {
+            max_size: 5,
+            ..options(&["127.0.0.1"])
+        })
+        .unwrap();
+
+        assert!(retriever.retrieve(&uri(&addr)).is_err());
+    }
+
+    #[test]
+    fn disk_cache() {
+        let cache =
+            std::env::temp_dir().join(format!("jsonschema_1c_cache_{}", std::process::id()));
+        let addr = serve(r#"{"type": "integer"}"#, 1);
+        let retriever = RemoteRetriever::new(RemoteOptions {
+            cache_directory: Some(cache.clone()),
+            ..options(&["127.0.0.1"])
+        })
+        .unwrap();
+
+        // The second call is served from the cache: the stand-in accepts a single request
+        assert_eq!(
+            retriever.retrieve(&uri(&addr)).unwrap(),
+            json!({"type": "integer"})
+        );
+        assert_eq!(
+            retriever.retrieve(&uri(&addr)).unwrap(),
+            json!({"type": "integer"})
+        );
+
+        std::fs::remove_dir_all(cache).unwrap();
+    }
+
+    #[test]
+    fn stable_cache_names() {
+        assert_eq!(fnv1a(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a("a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a("foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn wildcard_hosts() {
+        let options = options(&["*.example.com", "schemas.gov.ru"]);
+        assert!(options.is_host_allowed("api.example.com"));
+        assert!(options.is_host_allowed("Schemas.Gov.Ru"));
+        assert!(!options.is_host_allowed("example.com.evil.org"));
+        assert!(!options.is_host_allowed("gov.ru"));
+    }
+}
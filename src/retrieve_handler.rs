@@ -1,16 +1,64 @@
+use crate::remote_retriever::RemoteRetriever;
+use crate::schema_files;
 use jsonschema::Retrieve;
 use jsonschema::Uri;
 use serde_json::Value;
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, PoisonError};
 
 #[derive(Clone, Default)]
 pub struct RetrieveHandler {
     store: HashMap<Uri<String>, Value>,
+    file_root: Option<PathBuf>,
+    remote: Option<Arc<RemoteRetriever>>,
+    retrieved: Arc<Mutex<HashMap<Uri<String>, Value>>>,
 }
 
 impl RetrieveHandler {
     pub fn new(store: HashMap<Uri<String>, Value>) -> RetrieveHandler {
-        Self { store }
+        Self {
+            store,
+            ..Default::default()
+        }
+    }
+
+    /// Allows reading `file://` schemas located inside `root`.
+    pub fn with_file_root(mut self, root: PathBuf) -> RetrieveHandler {
+        self.file_root = Some(root);
+        self
+    }
+
+    /// Allows downloading `http(s)://` schemas.
+    pub fn with_remote(mut self, remote: RemoteRetriever) -> RetrieveHandler {
+        self.remote = Some(Arc::new(remote));
+        self
+    }
+
+    /// Schemas loaded from outside the store since the handler was created.
+    pub fn take_retrieved(&self) -> HashMap<Uri<String>, Value> {
+        std::mem::take(
+            &mut self
+                .retrieved
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner),
+        )
+    }
+
+    fn retrieve_file(
+        &self,
+        uri: &Uri<String>,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        let Some(root) = self.file_root.as_ref() else {
+            return Err(format!("Schema {uri} not found").into());
+        };
+
+        let path = schema_files::uri_path(uri).canonicalize()?;
+        if !path.starts_with(root.canonicalize()?) {
+            return Err(format!("Schema {uri} is outside of the base directory").into());
+        }
+
+        Ok(schema_files::read_schema(&path)?)
     }
 }
 
@@ -19,9 +67,64 @@ impl Retrieve for RetrieveHandler {
         &self,
         uri: &Uri<String>,
     ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
-        match self.store.get(uri.as_str()) {
-            Some(v) => Ok(v.clone()),
-            None => Err(format!("Schema {uri} not found").into()),
+        if let Some(v) = self.store.get(uri.as_str()) {
+            return Ok(v.clone());
         }
+
+        let value = match uri.scheme().as_str() {
+            "file" => self.retrieve_file(uri)?,
+            "http" | "https" => match self.remote.as_ref() {
+                Some(remote) => remote.retrieve(uri)?,
+                None => return Err(format!("Schema {uri} not found").into()),
+            },
+            _ => return Err(format!("Schema {uri} not found").into()),
+        };
+
+        self.retrieved
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(uri.clone(), value.clone());
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RetrieveHandler;
+    use crate::schema_files;
+    use serde_json::json;
+    use std::fs;
+    use std::path::Path;
+
+    fn build(root: &Path, schema: &serde_json::Value) -> Result<RetrieveHandler, String> {
+        let handler = RetrieveHandler::default().with_file_root(root.to_path_buf());
+        let base_uri = format!("{}/", schema_files::file_uri(root).unwrap());
+        jsonschema::options()
+            .with_base_uri(base_uri)
+            .with_retriever(handler.clone())
+            .build(schema)
+            .map(|_| handler)
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn relative_file_references() {
+        let dir = std::env::temp_dir().join(format!("jsonschema_1c_{}", std::process::id()));
+        let root = dir.join("root");
+        fs::create_dir_all(root.join("common")).unwrap();
+        fs::write(
+            root.join("common").join("address.json"),
+            json!({"type": "string"}).to_string(),
+        )
+        .unwrap();
+        fs::write(dir.join("secret.json"), "{}").unwrap();
+
+        let handler = build(&root, &json!({"$ref": "./common/address.json"})).unwrap();
+        assert_eq!(handler.take_retrieved().len(), 1);
+
+        let escaped = build(&root, &json!({"$ref": "../secret.json"}));
+        assert!(escaped.is_err());
+
+        fs::remove_dir_all(dir).unwrap();
     }
 }
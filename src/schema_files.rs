@@ -0,0 +1,186 @@
+use crate::errors::JsonSchema1CError;
+use jsonschema::Uri;
+use serde_json::Value;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const DEFAULT_MASK: &str = "*.json";
+
+pub fn read_schema(path: &Path) -> Result<Value, JsonSchema1CError> {
+    let content = fs::read(path).map_err(|error| JsonSchema1CError::FileRead {
+        path: path.display().to_string(),
+        error,
+    })?;
+
+    serde_json::from_slice(&content).map_err(|error| JsonSchema1CError::FileParse {
+        path: path.display().to_string(),
+        error,
+    })
+}
+
+/// Builds a `file://` URI from the absolute form of `path`.
+pub fn file_uri(path: &Path) -> Result<Uri<String>, JsonSchema1CError> {
+    let path = path
+        .canonicalize()
+        .map_err(|error| JsonSchema1CError::FileRead {
+            path: path.display().to_string(),
+            error,
+        })?;
+
+    let path = path.to_string_lossy();
+
+    #[cfg(windows)]
+    let path = {
+        // `canonicalize` returns verbatim paths like `\\?\C:\dir`
+        let path = path.strip_prefix(r"\\?\").unwrap_or(&path);
+        format!("/{}", path.replace('\\', "/"))
+    };
+
+    let mut uri = String::from("file://");
+    for byte in path.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~/:".contains(&byte) {
+            uri.push(char::from(byte));
+        } else {
+            let _ = write!(uri, "%{byte:02X}");
+        }
+    }
+
+    Uri::parse(uri.clone()).map_err(|_| JsonSchema1CError::InvalidUri(uri))
+}
+
+/// Converts a `file://` URI back to a filesystem path.
+pub fn uri_path(uri: &Uri<String>) -> PathBuf {
+    let path = uri.path().decode().to_string_lossy();
+
+    #[cfg(windows)]
+    let path = path.trim_start_matches('/').replace('/', "\\");
+
+    PathBuf::from(&*path)
+}
+
+/// Lists files in `dir` whose names match one of the `;`-separated wildcard masks.
+pub fn find_files(
+    dir: &Path,
+    mask: &str,
+    recursive: bool,
+) -> Result<Vec<PathBuf>, JsonSchema1CError> {
+    let masks: Vec<&str> = mask
+        .split(';')
+        .map(str::trim)
+        .filter(|m| !m.is_empty())
+        .collect();
+    let masks = if masks.is_empty() {
+        vec![DEFAULT_MASK]
+    } else {
+        masks
+    };
+
+    let mut files = Vec::new();
+    collect_files(dir, &masks, recursive, &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+fn collect_files(
+    dir: &Path,
+    masks: &[&str],
+    recursive: bool,
+    files: &mut Vec<PathBuf>,
+) -> Result<(), JsonSchema1CError> {
+    let read_error = |error| JsonSchema1CError::FileRead {
+        path: dir.display().to_string(),
+        error,
+    };
+
+    for entry in fs::read_dir(dir).map_err(read_error)? {
+        let entry = entry.map_err(read_error)?;
+        let file_type = entry.file_type().map_err(read_error)?;
+        let path = entry.path();
+
+        if file_type.is_dir() {
+            if recursive {
+                collect_files(&path, masks, recursive, files)?;
+            }
+            continue;
+        }
+
+        // Linked directories are not followed, they may lead back up the tree
+        if file_type.is_symlink() && path.is_dir() {
+            continue;
+        }
+
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if masks.iter().any(|mask| matches_mask(&name, mask)) {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Case-insensitive match with `*` and `?` wildcards.
+fn matches_mask(name: &str, mask: &str) -> bool {
+    let name: Vec<char> = name.to_lowercase().chars().collect();
+    let mask: Vec<char> = mask.to_lowercase().chars().collect();
+
+    let (mut n, mut m) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        if m < mask.len() && (mask[m] == '?' || mask[m] == name[n]) {
+            n += 1;
+            m += 1;
+        } else if m < mask.len() && mask[m] == '*' {
+            backtrack = Some((m, n));
+            m += 1;
+        } else if let Some((star, matched)) = backtrack {
+            m = star + 1;
+            n = matched + 1;
+            backtrack = Some((star, n));
+        } else {
+            return false;
+        }
+    }
+
+    mask[m..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_files, matches_mask};
+
+    #[test]
+    fn mask_matching() {
+        assert!(matches_mask("order.json", "*.json"));
+        assert!(matches_mask("Order.JSON", "*.json"));
+        assert!(matches_mask("order.schema.json", "*.schema.json"));
+        assert!(matches_mask("v1.json", "v?.json"));
+        assert!(matches_mask("anything", "*"));
+        assert!(!matches_mask("order.json.bak", "*.json"));
+        assert!(!matches_mask("v10.json", "v?.json"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn linked_directories_are_skipped() {
+        let dir = std::env::temp_dir().join(format!("jsonschema_1c_files_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("nested")).unwrap();
+        std::fs::write(dir.join("order.json"), "{}").unwrap();
+        std::fs::write(dir.join("nested/item.json"), "{}").unwrap();
+        std::os::unix::fs::symlink(&dir, dir.join("nested/loop")).unwrap();
+        std::os::unix::fs::symlink(dir.join("order.json"), dir.join("linked.json")).unwrap();
+
+        let files = find_files(&dir, "", true).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            files,
+            [
+                dir.join("linked.json"),
+                dir.join("nested/item.json"),
+                dir.join("order.json")
+            ]
+        );
+    }
+}
@@ -0,0 +1,279 @@
+use crate::errors::JsonSchema1CError;
+use jsonschema::{Draft, Evaluation, Registry, Uri};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// Base URI the validator assigns to a main schema without `$id`
+pub const DEFAULT_BASE_URI: &str = "json-schema:///";
+
+/// Longer `$ref` chains without descending into the instance are treated as cycles.
+const MAX_REF_DEPTH: usize = 64;
+
+/// Main schema together with the schemas it references, walked alongside an instance.
+pub struct SchemaWalker {
+    registry: Registry,
+    root: Arc<Uri<String>>,
+}
+
+/// Subschema with its evaluation path and the base URI its references resolve against.
+#[derive(Clone)]
+pub struct Scope<'a> {
+    pub schema: &'a Value,
+    pub path: String,
+    base: Arc<Uri<String>>,
+}
+
+/// `then`/`else` branches taken during an evaluation, by evaluation path and instance location.
+#[derive(Default)]
+pub struct Branches(HashSet<(String, String)>);
+
+impl Branches {
+    pub fn new(evaluation: &Evaluation) -> Result<Self, serde_json::Error> {
+        let list = serde_json::to_value(evaluation.list())?;
+        let nodes = list
+            .get("details")
+            .and_then(Value::as_array)
+            .map_or(&[][..], Vec::as_slice);
+
+        let taken = nodes
+            .iter()
+            .filter_map(|node| {
+                let path = node.get("evaluationPath")?.as_str()?;
+                let location = node.get("instanceLocation")?.as_str()?;
+                (path.ends_with("/then") || path.ends_with("/else"))
+                    .then(|| (path.to_string(), location.to_string()))
+            })
+            .collect();
+
+        Ok(Self(taken))
+    }
+
+    fn is_taken(&self, path: &str, instance_path: &str) -> bool {
+        self.0
+            .contains(&(path.to_string(), instance_path.to_string()))
+    }
+}
+
+impl SchemaWalker {
+    pub fn new(
+        schema: &Value,
+        base_uri: &str,
+        store: &HashMap<Uri<String>, Value>,
+        draft: Option<Draft>,
+    ) -> Result<Self, JsonSchema1CError> {
+        let draft = draft.unwrap_or_default();
+        let resource = |contents: &Value| draft.detect(contents).create_resource(contents.clone());
+
+        let resources = store
+            .iter()
+            .map(|(uri, contents)| (uri.as_str().to_string(), resource(contents)))
+            .chain([(base_uri.to_string(), resource(schema))]);
+
+        let registry = Registry::try_from_resources(resources)
+            .map_err(|e| JsonSchema1CError::SchemaCompile(e.to_string()))?;
+
+        let base = registry
+            .try_resolver(base_uri)
+            .map_err(|e| JsonSchema1CError::SchemaCompile(e.to_string()))?
+            .base_uri();
+
+        let root = match schema.get("$id").and_then(Value::as_str) {
+            Some(id) => registry
+                .resolve_against(&base.borrow(), id)
+                .map_err(|e| JsonSchema1CError::SchemaCompile(e.to_string()))?,
+            None => base,
+        };
+
+        Ok(Self { registry, root })
+    }
+
+    pub fn root(&self) -> Option<Scope<'_>> {
+        let resolver = self.registry.try_resolver(self.root.as_str()).ok()?;
+        let target = resolver.lookup("").ok()?;
+
+        Some(Scope {
+            schema: target.contents(),
+            path: String::new(),
+            base: self.root.clone(),
+        })
+    }
+
+    /// Subschema at an evaluation path from the root, such as the one of a validation error.
+    pub fn at_evaluation_path(&self, evaluation_path: &str) -> Option<Scope<'_>> {
+        let mut scope = self.root()?;
+
+        for token in evaluation_path.split('/').skip(1) {
+            let token = token.replace("~1", "/").replace("~0", "~");
+            scope = if token == "$ref" {
+                let reference = scope.schema.get("$ref")?.as_str()?;
+                self.resolve(&scope, reference)?
+            } else {
+                self.child(&scope, &[&token])?
+            };
+        }
+
+        Some(scope)
+    }
+
+    /// Subschema at `tokens` below `scope`, e.g. `["properties", "name"]`.
+    pub fn child<'a>(&'a self, scope: &Scope<'a>, tokens: &[&str]) -> Option<Scope<'a>> {
+        let mut schema = scope.schema;
+        let mut path = scope.path.clone();
+
+        for token in tokens {
+            schema = match schema {
+                Value::Object(map) => map.get(*token)?,
+                Value::Array(items) => items.get(token.parse::<usize>().ok()?)?,
+                _ => return None,
+            };
+            path.push('/');
+            path.push_str(&escape(token));
+        }
+
+        let base = match schema.get("$id").and_then(Value::as_str) {
+            Some(id) => self
+                .registry
+                .resolve_against(&scope.base.borrow(), id)
+                .ok()?,
+            None => scope.base.clone(),
+        };
+
+        Some(Scope { schema, path, base })
+    }
+
+    /// Target of a `$ref` found in `scope`.
+    pub fn resolve<'a>(&'a self, scope: &Scope<'a>, reference: &str) -> Option<Scope<'a>> {
+        let resolver = self.registry.try_resolver(scope.base.as_str()).ok()?;
+        let target = resolver.lookup(reference).ok()?;
+
+        Some(Scope {
+            schema: target.contents(),
+            path: format!("{}/$ref", scope.path),
+            base: target.resolver().base_uri(),
+        })
+    }
+
+    /// Subschemas applying to the same instance location as `scope`: the scope itself and
+    /// those reached through `$ref`, `allOf` and the taken `then`/`else` branch.
+    pub fn in_place<'a>(
+        &'a self,
+        scope: Scope<'a>,
+        branches: &Branches,
+        instance_path: &str,
+    ) -> Vec<Scope<'a>> {
+        let mut scopes = Vec::new();
+        self.collect_in_place(scope, branches, instance_path, 0, &mut scopes);
+        scopes
+    }
+
+    fn collect_in_place<'a>(
+        &'a self,
+        scope: Scope<'a>,
+        branches: &Branches,
+        instance_path: &str,
+        depth: usize,
+        scopes: &mut Vec<Scope<'a>>,
+    ) {
+        if !scope.schema.is_object() {
+            return;
+        }
+
+        let mut nested = Vec::new();
+
+        if let Some(reference) = scope.schema.get("$ref").and_then(Value::as_str) {
+            if depth < MAX_REF_DEPTH {
+                nested.extend(self.resolve(&scope, reference).map(|s| (s, depth + 1)));
+            }
+        }
+
+        if let Some(all_of) = scope.schema.get("allOf").and_then(Value::as_array) {
+            for index in 0..all_of.len() {
+                let index = index.to_string();
+                nested.extend(self.child(&scope, &["allOf", &index]).map(|s| (s, depth)));
+            }
+        }
+
+        if scope.schema.get("if").is_some() {
+            for branch in ["then", "else"] {
+                let path = format!("{}/{branch}", scope.path);
+                if branches.is_taken(&path, instance_path) {
+                    nested.extend(self.child(&scope, &[branch]).map(|s| (s, depth)));
+                }
+            }
+        }
+
+        scopes.push(scope);
+        for (scope, depth) in nested {
+            self.collect_in_place(scope, branches, instance_path, depth, scopes);
+        }
+    }
+
+    /// Schema declared for the property `name` in `properties`.
+    pub fn property<'a>(&'a self, scope: &Scope<'a>, name: &str) -> Option<Scope<'a>> {
+        self.child(scope, &["properties", name])
+    }
+
+    /// Schema applying to the array item at `index`.
+    pub fn item<'a>(&'a self, scope: &Scope<'a>, index: usize) -> Option<Scope<'a>> {
+        let position = index.to_string();
+
+        // 2020-12 `prefixItems` + `items`, or older tuple `items` + `additionalItems`
+        let (tuple, rest) = if scope.schema.get("prefixItems").is_some() {
+            ("prefixItems", "items")
+        } else if scope.schema.get("items").is_some_and(Value::is_array) {
+            ("items", "additionalItems")
+        } else {
+            return self.child(scope, &["items"]);
+        };
+
+        let tuple_len = scope
+            .schema
+            .get(tuple)
+            .and_then(Value::as_array)
+            .map_or(0, Vec::len);
+
+        if index < tuple_len {
+            self.child(scope, &[tuple, &position])
+        } else {
+            self.child(scope, &[rest])
+        }
+    }
+}
+
+/// Appends a reference token to a JSON Pointer.
+pub fn pointer_push(pointer: &str, token: &str) -> String {
+    format!("{pointer}/{}", escape(token))
+}
+
+fn escape(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// Walker over a schema with no stored schemas, and its validation errors as the walker sees
+/// them.
+#[cfg(test)]
+pub mod testing {
+    use super::{SchemaWalker, DEFAULT_BASE_URI};
+    use jsonschema::ValidationError;
+    use serde_json::Value;
+    use std::collections::HashMap;
+
+    pub fn walker(schema: &Value) -> SchemaWalker {
+        SchemaWalker::new(schema, DEFAULT_BASE_URI, &HashMap::new(), None).unwrap()
+    }
+
+    /// `inspect` applied to each error of `instance` against `schema`.
+    pub fn map_errors<T>(
+        schema: &Value,
+        instance: &Value,
+        inspect: impl Fn(&SchemaWalker, &ValidationError) -> T,
+    ) -> Vec<T> {
+        let walker = walker(schema);
+        let validator = jsonschema::validator_for(schema).unwrap();
+        validator
+            .iter_errors(instance)
+            .map(|error| inspect(&walker, &error))
+            .collect()
+    }
+}
@@ -0,0 +1,64 @@
+use crate::labels::{evaluation_tokens, keyword_position};
+use crate::schema_walker::SchemaWalker;
+use jsonschema::ValidationError;
+use serde_json::Value;
+
+pub const SEVERITY: &str = "x-severity";
+
+/// Whether the error comes from a rule marked `"x-severity": "warning"`. The mark applies to
+/// the subschema it is declared in and everything below it; the nearest one wins, so
+/// `"x-severity": "error"` turns a nested rule back into an error.
+pub fn is_warning(walker: &SchemaWalker, error: &ValidationError) -> bool {
+    let tokens = evaluation_tokens(error);
+    let position = keyword_position(error, &tokens);
+
+    (1..=position)
+        .rev()
+        .find_map(|end| {
+            walker
+                .at_evaluation_path(&tokens[..end].join("/"))?
+                .schema
+                .get(SEVERITY)
+                .and_then(Value::as_str)
+        })
+        .is_some_and(|severity| severity == "warning")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_warning;
+    use crate::schema_walker::testing::map_errors;
+    use serde_json::{json, Value};
+
+    fn warnings(schema: &Value, instance: &Value) -> Vec<bool> {
+        map_errors(schema, instance, is_warning)
+    }
+
+    #[test]
+    fn nearest_severity_wins() {
+        let schema = json!({
+            "$defs": {"phone": {"type": "string", "pattern": "^\\+7[0-9]{10}$"}},
+            "properties": {
+                "phone": {"$ref": "#/$defs/phone", "x-severity": "warning"},
+                "contact": {
+                    "x-severity": "warning",
+                    "properties": {
+                        "email": {"maxLength": 5},
+                        "name": {"type": "string", "x-severity": "error"}
+                    }
+                },
+                "inn": {"type": "string"}
+            }
+        });
+
+        assert_eq!(warnings(&schema, &json!({"phone": "123"})), vec![true]);
+        assert_eq!(
+            warnings(
+                &schema,
+                &json!({"contact": {"email": "a@b.com", "name": 1}})
+            ),
+            vec![true, false]
+        );
+        assert_eq!(warnings(&schema, &json!({"inn": 1})), vec![false]);
+    }
+}
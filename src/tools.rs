@@ -120,6 +120,23 @@ impl<'a> Param<'a> {
             .map_err(|_| Self::convert_err(ParamType::Bool))
     }
 
+    pub fn get_u32(&self) -> Result<u32, JsonSchema1CError> {
+        let number = match self.0.get_i32() {
+            Ok(n) => f64::from(n),
+            Err(_) => self
+                .0
+                .get_f64()
+                .map_err(|_| Self::convert_err(ParamType::Number))?,
+        };
+
+        if number.fract() != 0.0 || !(0.0..=f64::from(u32::MAX)).contains(&number) {
+            return Err(Self::convert_err(ParamType::Number));
+        }
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // Checked above
+        Ok(number as u32)
+    }
+
     pub fn get_str1c(&self) -> Result<&[u16], JsonSchema1CError> {
         self.0
             .get_str1c()
@@ -140,11 +157,19 @@ impl<'a, 'b> ParamMut<'a, 'b> {
             .map_err(|_| JsonSchema1CError::OutOfMemory)
     }
 
+    #[allow(clippy::unnecessary_wraps)]
     pub fn set_bool(&mut self, val: bool) -> ComponentResult {
         self.0.set_bool(val);
         Ok(())
     }
 
+    #[allow(clippy::unnecessary_wraps)]
+    pub fn set_i32(&mut self, val: i32) -> ComponentResult {
+        self.0.set_i32(val);
+        Ok(())
+    }
+
+    #[allow(clippy::unnecessary_wraps)]
     pub fn set_empty(&mut self) -> ComponentResult {
         self.0.set_empty();
         Ok(())
@@ -188,6 +213,12 @@ impl<'a, 'b> Params<'a, 'b> {
             .map_err(|_| Self::convert_err(index, ParamType::String))
     }
 
+    pub fn get_bool(&self, index: usize) -> Result<bool, JsonSchema1CError> {
+        self.get_variant(index)?
+            .get_bool()
+            .map_err(|_| Self::convert_err(index, ParamType::Bool))
+    }
+
     pub fn get_json_value(&self, index: usize) -> Result<serde_json::Value, JsonSchema1CError> {
         let variant = self.get_variant(index)?;
 
@@ -203,6 +234,26 @@ impl<'a, 'b> Params<'a, 'b> {
         Err(Self::convert_err(index, ParamType::StringOrBlob))
     }
 
+    /// JSON value together with the source text it was parsed from, as UTF-8.
+    pub fn get_json_with_source(
+        &self,
+        index: usize,
+    ) -> Result<(serde_json::Value, Vec<u8>), JsonSchema1CError> {
+        let variant = self.get_variant(index)?;
+
+        let source = if let Ok(s) = variant.get_string() {
+            s.into_bytes()
+        } else if let Ok(b) = variant.get_blob() {
+            b.to_vec()
+        } else {
+            return Err(Self::convert_err(index, ParamType::StringOrBlob));
+        };
+
+        let value = serde_json::from_slice(&source)
+            .map_err(|_| Self::convert_err(index, ParamType::Json))?;
+        Ok((value, source))
+    }
+
     pub fn get_uri(&self, index: usize) -> Result<jsonschema::Uri<String>, JsonSchema1CError> {
         let s = self.get_string(index)?;
         jsonschema::Uri::parse(s).map_err(|_| Self::convert_err(index, ParamType::Uri))